@@ -0,0 +1,318 @@
+use crate::account::token_store::{
+    write_private_file, SharedContents, StoreContents, StoreDocument, StoreError, StoredAccount,
+    TokenStore,
+};
+use crate::error::YggdrasilError;
+use crate::profile_id::ProfileId;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use std::fs;
+use std::path::PathBuf;
+
+/// The bytes every encrypted credential file starts with.
+const MAGIC: [u8; 4] = *b"YGGE";
+
+/// The version of the encrypted file format written by this crate.
+const FORMAT_VERSION: u8 = 1;
+
+/// The length of the salt fed to the key derivation function.
+const SALT_LENGTH: usize = 16;
+
+/// The length of an XChaCha20-Poly1305 nonce.
+const NONCE_LENGTH: usize = 24;
+
+/// The length of the header: magic, version, three KDF parameters, salt and nonce.
+const HEADER_LENGTH: usize = MAGIC.len() + 1 + 3 * 4 + SALT_LENGTH + NONCE_LENGTH;
+
+/// The most memory the key derivation may use, in KiB (256 MiB).
+const MAX_MEMORY_KIB: u32 = 256 * 1024;
+
+/// The most passes over the memory the key derivation may make.
+const MAX_ITERATIONS: u32 = 16;
+
+/// The most lanes the key derivation may use.
+const MAX_PARALLELISM: u32 = 16;
+
+/// The cost parameters of the Argon2id key derivation.
+///
+/// Each parameter is capped (256 MiB, 16 passes, 16 lanes) so that a file with a forged header
+/// cannot exhaust the memory or the time of the process before the header is authenticated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// The memory used, in KiB.
+    pub memory_kib: u32,
+
+    /// The number of passes over the memory.
+    pub iterations: u32,
+
+    /// The number of lanes.
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// Returns the parameters recommended by OWASP for Argon2id: 19 MiB, 2 passes, 1 lane.
+    fn default() -> Self {
+        KdfParams {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Checks that the parameters are within the bounds accepted by this crate.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()`, or a `StoreError` naming the parameter out of range.
+    fn check_bounds(&self) -> Result<(), StoreError> {
+        let bounds = [
+            ("memory", self.memory_kib, MAX_MEMORY_KIB),
+            ("iterations", self.iterations, MAX_ITERATIONS),
+            ("parallelism", self.parallelism, MAX_PARALLELISM),
+        ];
+        match bounds.iter().find(|(_, value, max)| value > max) {
+            Some((name, value, max)) => Err(StoreError::InvalidKdfParams(format!(
+                "{} is {}, above the maximum of {}",
+                name, value, max
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Derives a key from a passphrase with these parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `passphrase` - The passphrase of the user.
+    /// * `salt` - The salt stored in the file header.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the key, or a `StoreError` if the parameters are out of range or
+    /// above the maximums of this crate.
+    fn derive_key(&self, passphrase: &str, salt: &[u8]) -> Result<Key, StoreError> {
+        let invalid = |error: argon2::Error| StoreError::InvalidKdfParams(error.to_string());
+
+        // The parameters of an existing file are read before the header is authenticated
+        self.check_bounds()?;
+
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(invalid)?;
+        let mut key = Key::default();
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(invalid)?;
+        Ok(key)
+    }
+}
+
+/// A `TokenStore` persisting accounts to a file encrypted with a passphrase.
+///
+/// The key is derived from the passphrase with Argon2id and the accounts are encrypted with
+/// XChaCha20-Poly1305. The header, holding the format version, the KDF parameters, the salt
+/// and the nonce, is authenticated along with the accounts, so any change to the file is
+/// detected on load.
+pub struct EncryptedFileTokenStore {
+    /// The path of the encrypted file.
+    path: PathBuf,
+
+    /// The header prefix written before each nonce: magic, version, KDF parameters and salt.
+    header: Vec<u8>,
+
+    /// The cipher keyed with the derived key.
+    cipher: XChaCha20Poly1305,
+
+    /// The stored accounts and the selection, mirroring the file.
+    contents: SharedContents,
+}
+
+impl EncryptedFileTokenStore {
+    /// Opens an `EncryptedFileTokenStore` with the default KDF parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the encrypted file, which is created on the first save.
+    /// * `passphrase` - The passphrase the key is derived from.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `EncryptedFileTokenStore` on success or a `YggdrasilError` if
+    /// the file exists but cannot be read or decrypted.
+    pub fn open(
+        path: impl Into<PathBuf>,
+        passphrase: &str,
+    ) -> Result<EncryptedFileTokenStore, YggdrasilError> {
+        EncryptedFileTokenStore::open_with_params(path, passphrase, KdfParams::default())
+    }
+
+    /// Opens an `EncryptedFileTokenStore` with custom KDF parameters.
+    ///
+    /// The parameters only apply to new files: an existing file is always decrypted with the
+    /// parameters recorded in its header, and keeps them when rewritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the encrypted file, which is created on the first save.
+    /// * `passphrase` - The passphrase the key is derived from.
+    /// * `params` - The KDF parameters for a new file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `EncryptedFileTokenStore` on success or a `YggdrasilError` if
+    /// the file exists but cannot be read or decrypted.
+    pub fn open_with_params(
+        path: impl Into<PathBuf>,
+        passphrase: &str,
+        params: KdfParams,
+    ) -> Result<EncryptedFileTokenStore, YggdrasilError> {
+        let path = path.into();
+
+        if !path.exists() {
+            let mut salt = [0; SALT_LENGTH];
+            OsRng.fill_bytes(&mut salt);
+            let key = params.derive_key(passphrase, &salt)?;
+            return Ok(EncryptedFileTokenStore {
+                path,
+                header: encode_header(&params, &salt),
+                cipher: XChaCha20Poly1305::new(&key),
+                contents: SharedContents::default(),
+            });
+        }
+
+        let content = fs::read(&path)?;
+        if content.len() < HEADER_LENGTH || content[..MAGIC.len()] != MAGIC {
+            return Err(StoreError::NotEncrypted.into());
+        }
+        let (header, ciphertext) = content.split_at(HEADER_LENGTH);
+        if header[MAGIC.len()] != FORMAT_VERSION {
+            return Err(StoreError::UnsupportedVersion(header[MAGIC.len()].into()).into());
+        }
+
+        let read_u32 = |offset: usize| {
+            let offset = MAGIC.len() + 1 + offset * 4;
+            u32::from_le_bytes(header[offset..offset + 4].try_into().unwrap())
+        };
+        let params = KdfParams {
+            memory_kib: read_u32(0),
+            iterations: read_u32(1),
+            parallelism: read_u32(2),
+        };
+        let prefix = &header[..HEADER_LENGTH - NONCE_LENGTH];
+        let salt = &prefix[prefix.len() - SALT_LENGTH..];
+        let nonce = XNonce::from_slice(&header[HEADER_LENGTH - NONCE_LENGTH..]);
+
+        let key = params.derive_key(passphrase, salt)?;
+        let cipher = XChaCha20Poly1305::new(&key);
+        let plaintext = cipher
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| StoreError::Decryption)?;
+        let document: StoreDocument = serde_json::from_slice(&plaintext)?;
+
+        Ok(EncryptedFileTokenStore {
+            path,
+            header: prefix.to_vec(),
+            cipher,
+            contents: SharedContents::new(document.into_contents()?),
+        })
+    }
+
+    /// Encrypts the accounts and the selection under a fresh nonce and writes them to the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The contents to write.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    fn write(&self, contents: &StoreContents) -> Result<(), YggdrasilError> {
+        let plaintext = serde_json::to_vec(&StoreDocument::new(contents))?;
+
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let mut content = self.header.clone();
+        content.extend_from_slice(&nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: &content,
+                },
+            )
+            .map_err(|_| StoreError::Encryption)?;
+        content.extend_from_slice(&ciphertext);
+
+        write_private_file(&self.path, &content)
+    }
+}
+
+impl TokenStore for EncryptedFileTokenStore {
+    fn load(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+    ) -> Result<Option<StoredAccount>, YggdrasilError> {
+        Ok(self.contents.load(server, profile_id))
+    }
+
+    fn save(&self, account: StoredAccount) -> Result<(), YggdrasilError> {
+        self.contents.update(
+            |contents| contents.save(account),
+            |contents| self.write(contents),
+        )
+    }
+
+    fn remove(&self, server: &str, profile_id: &ProfileId) -> Result<(), YggdrasilError> {
+        self.contents.update(
+            |contents| contents.remove(server, profile_id),
+            |contents| self.write(contents),
+        )
+    }
+
+    fn list(&self) -> Result<Vec<StoredAccount>, YggdrasilError> {
+        Ok(self.contents.list())
+    }
+
+    fn selected(&self) -> Result<Option<(String, ProfileId)>, YggdrasilError> {
+        Ok(self.contents.selected())
+    }
+
+    fn select(&self, selection: Option<(&str, &ProfileId)>) -> Result<(), YggdrasilError> {
+        self.contents.update(
+            |contents| contents.select(selection),
+            |contents| self.write(contents),
+        )
+    }
+}
+
+/// Encodes the part of the header that precedes the nonce.
+///
+/// # Arguments
+///
+/// * `params` - The KDF parameters.
+/// * `salt` - The KDF salt.
+///
+/// # Returns
+///
+/// The magic, the format version, the KDF parameters in little endian and the salt.
+fn encode_header(params: &KdfParams, salt: &[u8]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(HEADER_LENGTH - NONCE_LENGTH);
+    header.extend_from_slice(&MAGIC);
+    header.push(FORMAT_VERSION);
+    header.extend_from_slice(&params.memory_kib.to_le_bytes());
+    header.extend_from_slice(&params.iterations.to_le_bytes());
+    header.extend_from_slice(&params.parallelism.to_le_bytes());
+    header.extend_from_slice(salt);
+    header
+}
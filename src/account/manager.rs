@@ -0,0 +1,618 @@
+use crate::account::offline::{OfflineAccount, OFFLINE_SERVER};
+use crate::account::session::{is_forbidden, Session, SessionStatus, SessionTokens};
+use crate::account::token_store::{StoredAccount, TokenStore};
+use crate::auth_agent::AuthAgent;
+use crate::auth_profile::AuthProfile;
+use crate::client::client::{parse_base_url, AuthClient};
+use crate::error::YggdrasilError;
+use crate::profile_id::ProfileId;
+use crate::AuthResponse;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// The number of events buffered for each subscriber before the oldest are dropped.
+const EVENT_CAPACITY: usize = 64;
+
+/// A change to the accounts held by an `AccountManager`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountEvent {
+    /// An account was added, or replaced after logging in again.
+    Added {
+        /// The base URL of the server of the account.
+        server: String,
+
+        /// The UUID of the profile of the account.
+        profile_id: ProfileId,
+    },
+
+    /// An account was removed.
+    Removed {
+        /// The base URL of the server of the account.
+        server: String,
+
+        /// The UUID of the profile of the account.
+        profile_id: ProfileId,
+    },
+
+    /// An account was selected.
+    Selected {
+        /// The base URL of the server of the account.
+        server: String,
+
+        /// The UUID of the profile of the account.
+        profile_id: ProfileId,
+    },
+
+    /// The access token of an account expired and was refreshed.
+    Refreshed {
+        /// The base URL of the server of the account.
+        server: String,
+
+        /// The UUID of the profile of the account.
+        profile_id: ProfileId,
+    },
+
+    /// The tokens of an account can no longer be refreshed; the user must log in again.
+    TokenInvalidated {
+        /// The base URL of the server of the account.
+        server: String,
+
+        /// The UUID of the profile of the account.
+        profile_id: ProfileId,
+    },
+}
+
+/// The outcome of `AccountManager::add_account`.
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    /// The user logged in and the account was stored.
+    LoggedIn(StoredAccount),
+
+    /// The user owns several profiles and must choose one with
+    /// `AccountManager::choose_profile`.
+    ProfileRequired(AuthResponse),
+}
+
+/// Manages the accounts of a launcher across several Yggdrasil servers.
+///
+/// Accounts are persisted in a `TokenStore` and identified by the base URL of their server
+/// and the UUID of their profile, so logging in again with the same profile replaces the
+/// existing account instead of adding a duplicate. A user is kept once per server: binding
+/// their login to another profile replaces the account of their previous profile, whose
+/// tokens the server no longer honours. Offline accounts are stored under `OFFLINE_SERVER`
+/// and go through the same methods. The selected account is recorded in the store, so it
+/// survives launcher restarts, and an `AccountEvent` is broadcast whenever an account changes.
+pub struct AccountManager {
+    /// The store the accounts are persisted in.
+    store: Arc<dyn TokenStore>,
+
+    /// The client token sent when logging in.
+    client_token: String,
+
+    /// The clients used for each server, keyed by base URL.
+    clients: Mutex<HashMap<String, Arc<AuthClient>>>,
+
+    /// Serialises the operations that store or replace tokens, so a token is refreshed at most
+    /// once and logins do not race with refreshes or removals.
+    token_lock: tokio::sync::Mutex<()>,
+
+    /// The channel account events are broadcast on.
+    events: broadcast::Sender<AccountEvent>,
+}
+
+impl AccountManager {
+    /// Creates a new `AccountManager`.
+    ///
+    /// The accounts and the selection recorded in the store are picked up as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store the accounts are persisted in.
+    /// * `client_token` - The client token sent when logging in, which should be stable across
+    ///   launcher restarts.
+    ///
+    /// # Returns
+    ///
+    /// A new `AccountManager` instance.
+    pub fn new(store: Arc<dyn TokenStore>, client_token: &str) -> AccountManager {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        AccountManager {
+            store,
+            client_token: client_token.to_string(),
+            clients: Mutex::new(HashMap::new()),
+            token_lock: tokio::sync::Mutex::new(()),
+            events,
+        }
+    }
+
+    /// Registers the client used for the server at its base URL.
+    ///
+    /// Servers without a registered client are reached with a client created by
+    /// `AuthClient::new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to use for its server.
+    pub fn register_client(&self, client: Arc<AuthClient>) {
+        let server = client.base_url().to_string();
+        self.clients.lock().unwrap().insert(server, client);
+    }
+
+    /// Subscribes to the events of the accounts.
+    ///
+    /// # Returns
+    ///
+    /// A receiver for every `AccountEvent` broadcast after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<AccountEvent> {
+        self.events.subscribe()
+    }
+
+    /// Lists all accounts.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the accounts, ordered by server and profile.
+    pub fn accounts(&self) -> Result<Vec<StoredAccount>, YggdrasilError> {
+        self.store.list()
+    }
+
+    /// Returns the selected account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the selected account, or `None` if no account is selected.
+    pub fn selected(&self) -> Result<Option<StoredAccount>, YggdrasilError> {
+        match self.store.selected()? {
+            Some((server, profile_id)) => self.store.load(&server, &profile_id),
+            None => Ok(None),
+        }
+    }
+
+    /// Selects an account.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the server of the account.
+    /// * `profile_id` - The UUID of the profile of the account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the selected account, or `None` if it is not stored, in which case
+    /// the selection is unchanged.
+    pub fn select(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+    ) -> Result<Option<StoredAccount>, YggdrasilError> {
+        let server = normalize_server(server)?;
+        let account = self.store.load(&server, profile_id)?;
+        if let Some(account) = &account {
+            self.set_selected(account)?;
+        }
+        Ok(account)
+    }
+
+    /// Logs in to a server and stores the account.
+    ///
+    /// The first account added is selected automatically.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the server.
+    /// * `username` - The username or email address of the user.
+    /// * `password` - The password of the user.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `LoginOutcome` on success or a `YggdrasilError` if the login
+    /// was rejected.
+    pub async fn add_account(
+        &self,
+        server: &str,
+        username: &str,
+        password: &str,
+    ) -> Result<LoginOutcome, YggdrasilError> {
+        let server = normalize_server(server)?;
+        let _guard = self.token_lock.lock().await;
+        let response = self
+            .client(&server)?
+            .authenticate(
+                AuthAgent::new("Minecraft".to_string(), 1),
+                username,
+                password,
+                &self.client_token,
+                true,
+            )
+            .await?;
+
+        match StoredAccount::from_auth_response(&server, &response) {
+            Some(mut account) => {
+                account.username = Some(username.to_string());
+                self.save_added(account).map(LoginOutcome::LoggedIn)
+            }
+            None => Ok(LoginOutcome::ProfileRequired(response)),
+        }
+    }
+
+    /// Adds an offline account, stored under `OFFLINE_SERVER`.
+    ///
+    /// Adding the same player name twice keeps a single account, since its UUID is derived
+    /// from the name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The player name.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the stored account, or a `YggdrasilError` if the name is invalid.
+    pub fn add_offline_account(&self, name: &str) -> Result<StoredAccount, YggdrasilError> {
+        let response = OfflineAccount::new(name)?.to_auth_response();
+        let mut account = StoredAccount::from_auth_response(OFFLINE_SERVER, &response)
+            .expect("offline accounts always select their profile");
+        account.username = Some(name.to_string());
+        self.save_added(account)
+    }
+
+    /// Binds a login that returned `LoginOutcome::ProfileRequired` to one of its profiles and
+    /// stores the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the server.
+    /// * `username` - The username the user logged in with.
+    /// * `response` - The response carried by `LoginOutcome::ProfileRequired`.
+    /// * `profile` - The profile chosen by the user, one of the available profiles.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the stored account on success or a `YggdrasilError` on failure,
+    /// such as `ProfileNotAvailable` if the profile is not one of the available profiles.
+    pub async fn choose_profile(
+        &self,
+        server: &str,
+        username: &str,
+        response: &AuthResponse,
+        profile: &AuthProfile,
+    ) -> Result<StoredAccount, YggdrasilError> {
+        let server = normalize_server(server)?;
+        let _guard = self.token_lock.lock().await;
+        let account = self
+            .bind_profile(&server, username, response, &profile.id)
+            .await?;
+        self.save_added(account)
+    }
+
+    /// Logs in again to an account whose tokens can no longer be refreshed.
+    ///
+    /// The stored username is used, falling back to the profile name for accounts stored
+    /// without one. Offline accounts are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the server of the account.
+    /// * `profile_id` - The UUID of the profile of the account.
+    /// * `password` - The password of the user.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the updated account, or `None` if the account is not stored or
+    /// its profile no longer belongs to the user.
+    pub async fn relogin(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+        password: &str,
+    ) -> Result<Option<StoredAccount>, YggdrasilError> {
+        let server = normalize_server(server)?;
+        let _guard = self.token_lock.lock().await;
+
+        let account = match self.store.load(&server, profile_id)? {
+            Some(account) if server == OFFLINE_SERVER => return Ok(Some(account)),
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let username = match (&account.username, &account.selected_profile) {
+            (Some(username), _) => username.clone(),
+            (None, Some(profile)) => profile.name.clone(),
+            (None, None) => return Ok(None),
+        };
+
+        let response = self
+            .client(&server)?
+            .authenticate(
+                AuthAgent::new("Minecraft".to_string(), 1),
+                &username,
+                password,
+                &self.client_token,
+                true,
+            )
+            .await?;
+        if !response
+            .available_profiles
+            .iter()
+            .chain(response.selected_profile.iter())
+            .any(|profile| profile.id == *profile_id)
+        {
+            return Ok(None);
+        }
+
+        let account = self
+            .bind_profile(&server, &username, &response, profile_id)
+            .await?;
+        self.save_added(account).map(Some)
+    }
+
+    /// Makes sure the access token of an account is valid, refreshing it if it has expired.
+    ///
+    /// Refreshed tokens are stored and broadcast as `AccountEvent::Refreshed`; tokens that can
+    /// no longer be refreshed are broadcast as `AccountEvent::TokenInvalidated`. Offline
+    /// accounts are always valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the server of the account.
+    /// * `profile_id` - The UUID of the profile of the account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SessionStatus`, or `None` if the account is not stored.
+    pub async fn ensure_valid(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+    ) -> Result<Option<SessionStatus>, YggdrasilError> {
+        let server = normalize_server(server)?;
+        let _guard = self.token_lock.lock().await;
+
+        let mut account = match self.store.load(&server, profile_id)? {
+            Some(_) if server == OFFLINE_SERVER => return Ok(Some(SessionStatus::Valid)),
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let session = Session::new(
+            self.client(&server)?,
+            SessionTokens {
+                access_token: account.access_token.clone(),
+                client_token: account.client_token.clone(),
+                selected_profile: account.selected_profile.clone(),
+            },
+        );
+
+        let status = session.ensure_valid().await?;
+        match status {
+            SessionStatus::Valid => {}
+            SessionStatus::Refreshed => {
+                let tokens = session.tokens().await;
+                account.access_token = tokens.access_token;
+                account.client_token = tokens.client_token;
+                account.selected_profile = tokens.selected_profile;
+                self.store.save(account.clone())?;
+                self.send(AccountEvent::Refreshed {
+                    server: account.server,
+                    profile_id: account.profile_id,
+                });
+            }
+            SessionStatus::ReloginRequired => self.send(AccountEvent::TokenInvalidated {
+                server: account.server,
+                profile_id: account.profile_id,
+            }),
+        }
+        Ok(Some(status))
+    }
+
+    /// Removes an account, invalidating its tokens on the server first.
+    ///
+    /// Tokens the server already rejects do not prevent the removal. Offline accounts are
+    /// removed without contacting any server. A refresh in progress for the account finishes
+    /// before it is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the server of the account.
+    /// * `profile_id` - The UUID of the profile of the account.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the removed account, or `None` if it was not stored.
+    pub async fn remove(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+    ) -> Result<Option<StoredAccount>, YggdrasilError> {
+        let server = normalize_server(server)?;
+        // A refresh in flight would save the account back after its removal
+        let _guard = self.token_lock.lock().await;
+
+        let account = match self.store.load(&server, profile_id)? {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+
+        if server != OFFLINE_SERVER {
+            match self
+                .client(&server)?
+                .invalidate(&account.access_token, &account.client_token)
+                .await
+            {
+                Err(error) if !is_forbidden(&error) => return Err(error),
+                _ => {}
+            }
+        }
+
+        // Removing the selected account also clears the selection
+        self.store.remove(&server, profile_id)?;
+        self.send(AccountEvent::Removed {
+            server: account.server.clone(),
+            profile_id: account.profile_id,
+        });
+        Ok(Some(account))
+    }
+
+    /// Returns the client used for a server, creating it if none is registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The normalised base URL of the server.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the client.
+    fn client(&self, server: &str) -> Result<Arc<AuthClient>, YggdrasilError> {
+        let mut clients = self.clients.lock().unwrap();
+        if let Some(client) = clients.get(server) {
+            return Ok(client.clone());
+        }
+
+        let client = Arc::new(AuthClient::new(server.to_string(), None)?);
+        clients.insert(server.to_string(), client.clone());
+        Ok(client)
+    }
+
+    /// Binds the tokens of a login to a profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The normalised base URL of the server.
+    /// * `username` - The username the user logged in with.
+    /// * `response` - The response to the login.
+    /// * `profile_id` - The UUID of the profile to bind.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the account bound to the profile, or
+    /// `YggdrasilError::ProfileNotAvailable` if the profile is not one of the available
+    /// profiles.
+    async fn bind_profile(
+        &self,
+        server: &str,
+        username: &str,
+        response: &AuthResponse,
+        profile_id: &ProfileId,
+    ) -> Result<StoredAccount, YggdrasilError> {
+        if let Some(mut account) = StoredAccount::from_auth_response(server, response) {
+            if account.profile_id == *profile_id {
+                account.username = Some(username.to_string());
+                return Ok(account);
+            }
+        }
+
+        let profile = response
+            .available_profiles
+            .iter()
+            .find(|profile| profile.id == *profile_id)
+            .cloned()
+            .ok_or(YggdrasilError::ProfileNotAvailable(*profile_id))?;
+        let refreshed = self
+            .client(server)?
+            .refresh(
+                &response.access_token,
+                &response.client_token,
+                true,
+                Some(profile.clone()),
+            )
+            .await?;
+
+        let selected_profile = refreshed.selected_profile.unwrap_or(profile);
+        Ok(StoredAccount {
+            server: server.to_string(),
+            profile_id: selected_profile.id,
+            access_token: refreshed.access_token,
+            client_token: refreshed.client_token,
+            selected_profile: Some(selected_profile),
+            user: refreshed.user.or_else(|| response.user.clone()),
+            username: Some(username.to_string()),
+        })
+    }
+
+    /// Stores an account that was logged in, selecting it if no account is selected.
+    ///
+    /// Accounts of the same user on the same server for other profiles are removed, and the
+    /// new account takes over their selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `account` - The account to store.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the stored account.
+    fn save_added(&self, account: StoredAccount) -> Result<StoredAccount, YggdrasilError> {
+        if let Some(user) = &account.user {
+            for duplicate in self.store.list()? {
+                let same_user = duplicate.server == account.server
+                    && duplicate.key() != account.key()
+                    && duplicate.user.as_ref().map(|other| &other.id) == Some(&user.id);
+                if !same_user {
+                    continue;
+                }
+
+                self.store
+                    .remove(&duplicate.server, &duplicate.profile_id)?;
+                self.send(AccountEvent::Removed {
+                    server: duplicate.server,
+                    profile_id: duplicate.profile_id,
+                });
+            }
+        }
+
+        self.store.save(account.clone())?;
+        self.send(AccountEvent::Added {
+            server: account.server.clone(),
+            profile_id: account.profile_id,
+        });
+
+        if self.store.selected()?.is_none() {
+            self.set_selected(&account)?;
+        }
+        Ok(account)
+    }
+
+    /// Records an account as selected in the store and broadcasts the change.
+    ///
+    /// # Arguments
+    ///
+    /// * `account` - The account to select.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` if the store failed.
+    fn set_selected(&self, account: &StoredAccount) -> Result<(), YggdrasilError> {
+        self.store
+            .select(Some((&account.server, &account.profile_id)))?;
+        self.send(AccountEvent::Selected {
+            server: account.server.clone(),
+            profile_id: account.profile_id,
+        });
+        Ok(())
+    }
+
+    /// Broadcasts an event to the subscribers, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event to broadcast.
+    fn send(&self, event: AccountEvent) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.events.send(event);
+    }
+}
+
+/// Normalises the base URL of a server, so it matches the base URL of its client.
+///
+/// `OFFLINE_SERVER` is returned unchanged.
+///
+/// # Arguments
+///
+/// * `server` - The base URL of the server.
+///
+/// # Returns
+///
+/// A `Result` containing the normalised base URL.
+fn normalize_server(server: &str) -> Result<String, YggdrasilError> {
+    if server == OFFLINE_SERVER {
+        return Ok(server.to_string());
+    }
+    Ok(parse_base_url(server)?.to_string())
+}
@@ -0,0 +1,115 @@
+use crate::auth_profile::AuthProfile;
+use crate::error::YggdrasilError;
+use crate::profile_id::ProfileId;
+use crate::AuthResponse;
+use md5::{Digest, Md5};
+
+/// The server recorded for offline accounts in a `TokenStore`.
+pub const OFFLINE_SERVER: &str = "offline";
+
+/// The shortest player name accepted by the vanilla client.
+const MIN_NAME_LENGTH: usize = 3;
+
+/// The longest player name accepted by the vanilla client.
+const MAX_NAME_LENGTH: usize = 16;
+
+/// An account for offline play, which never contacts an authentication server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineAccount {
+    /// The profile of the account, with the UUID derived from its name.
+    profile: AuthProfile,
+}
+
+impl OfflineAccount {
+    /// Creates a new `OfflineAccount` for a player name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The player name: 3 to 16 ASCII letters, digits or underscores.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `OfflineAccount`, or a `YggdrasilError` if the name is invalid.
+    pub fn new(name: &str) -> Result<OfflineAccount, YggdrasilError> {
+        validate_player_name(name)?;
+        Ok(OfflineAccount {
+            profile: AuthProfile::new(name.to_string(), offline_uuid(name)),
+        })
+    }
+
+    /// Returns the profile of the account.
+    ///
+    /// # Returns
+    ///
+    /// The `AuthProfile` with the player name and the derived UUID.
+    pub fn profile(&self) -> &AuthProfile {
+        &self.profile
+    }
+
+    /// Returns the access token passed to the game for the account.
+    ///
+    /// Offline accounts have no real token; the placeholder is the unsigned profile UUID, so
+    /// it is stable across launches.
+    ///
+    /// # Returns
+    ///
+    /// The access token placeholder.
+    pub fn access_token(&self) -> String {
+        self.profile.id.to_string()
+    }
+
+    /// Converts the account into the shape returned by `authenticate`.
+    ///
+    /// # Returns
+    ///
+    /// An `AuthResponse` with the placeholder as both tokens and the profile selected.
+    pub fn to_auth_response(&self) -> AuthResponse {
+        AuthResponse {
+            access_token: self.access_token(),
+            client_token: self.access_token(),
+            available_profiles: vec![self.profile.clone()],
+            selected_profile: Some(self.profile.clone()),
+            user: None,
+        }
+    }
+}
+
+/// Derives the UUID the vanilla server assigns to a player name in offline mode.
+///
+/// This is Java's `UUID.nameUUIDFromBytes("OfflinePlayer:" + name)`: the MD5 hash of the
+/// string, with the version set to 3 and the IETF variant.
+///
+/// # Arguments
+///
+/// * `name` - The player name.
+///
+/// # Returns
+///
+/// The offline `ProfileId` of the player.
+pub fn offline_uuid(name: &str) -> ProfileId {
+    let mut hash = Md5::digest(format!("OfflinePlayer:{}", name).as_bytes());
+    hash[6] = (hash[6] & 0x0f) | 0x30;
+    hash[8] = (hash[8] & 0x3f) | 0x80;
+
+    ProfileId::from_u128(u128::from_be_bytes(hash.into()))
+}
+
+/// Checks that a player name is accepted by the vanilla client.
+///
+/// # Arguments
+///
+/// * `name` - The player name.
+///
+/// # Returns
+///
+/// A `Result` containing `()` if the name is 3 to 16 ASCII letters, digits or underscores.
+pub fn validate_player_name(name: &str) -> Result<(), YggdrasilError> {
+    let valid = (MIN_NAME_LENGTH..=MAX_NAME_LENGTH).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(YggdrasilError::InvalidPlayerName(name.to_string()))
+    }
+}
@@ -0,0 +1,149 @@
+use crate::auth_profile::AuthProfile;
+use crate::client::client::AuthClient;
+use crate::error::YggdrasilError;
+use crate::AuthResponse;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// The tokens held by a `Session`.
+#[derive(Debug, Clone)]
+pub struct SessionTokens {
+    /// The access token of the user.
+    pub access_token: String,
+
+    /// The client token the access token is bound to.
+    pub client_token: String,
+
+    /// The profile selected by the user, if any.
+    pub selected_profile: Option<AuthProfile>,
+}
+
+/// The outcome of `Session::ensure_valid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// The access token was still valid.
+    Valid,
+
+    /// The access token had expired and was refreshed.
+    Refreshed,
+
+    /// The tokens can no longer be refreshed; the user must log in with a password again.
+    ReloginRequired,
+}
+
+/// A logged-in session that keeps its access token valid.
+///
+/// The session owns the tokens returned by `authenticate` and swaps in new ones whenever
+/// `ensure_valid` has to refresh them. Concurrent calls to `ensure_valid` are serialised, so
+/// the tokens are refreshed at most once.
+pub struct Session {
+    /// The client used to validate and refresh the tokens.
+    client: Arc<AuthClient>,
+
+    /// The current tokens.
+    tokens: Mutex<SessionTokens>,
+}
+
+impl Session {
+    /// Creates a new `Session` holding the given tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client used to validate and refresh the tokens.
+    /// * `tokens` - The tokens of the session.
+    ///
+    /// # Returns
+    ///
+    /// A new `Session` instance.
+    pub fn new(client: Arc<AuthClient>, tokens: SessionTokens) -> Session {
+        Session {
+            client,
+            tokens: Mutex::new(tokens),
+        }
+    }
+
+    /// Creates a new `Session` from the response to an `authenticate` request.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client used to validate and refresh the tokens.
+    /// * `response` - The response returned by `authenticate`.
+    ///
+    /// # Returns
+    ///
+    /// A new `Session` instance.
+    pub fn from_auth_response(client: Arc<AuthClient>, response: &AuthResponse) -> Session {
+        Session::new(
+            client,
+            SessionTokens {
+                access_token: response.access_token.clone(),
+                client_token: response.client_token.clone(),
+                selected_profile: response.selected_profile.clone(),
+            },
+        )
+    }
+
+    /// Returns a copy of the current tokens.
+    ///
+    /// # Returns
+    ///
+    /// The current `SessionTokens`.
+    pub async fn tokens(&self) -> SessionTokens {
+        self.tokens.lock().await.clone()
+    }
+
+    /// Makes sure the access token is valid, refreshing it if it has expired.
+    ///
+    /// The token is validated first; if the server rejects it, it is refreshed and the new
+    /// tokens replace the old ones. If the refresh is rejected too, the user must log in with
+    /// a password again.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SessionStatus` on success or a `YggdrasilError` if the server
+    /// could not be reached or answered unexpectedly.
+    pub async fn ensure_valid(&self) -> Result<SessionStatus, YggdrasilError> {
+        let mut tokens = self.tokens.lock().await;
+
+        match self.client.validate(&tokens.access_token).await {
+            Ok(()) => return Ok(SessionStatus::Valid),
+            Err(error) if !is_forbidden(&error) => return Err(error),
+            Err(_) => {}
+        }
+
+        let response = self
+            .client
+            .refresh(&tokens.access_token, &tokens.client_token, false, None)
+            .await;
+        match response {
+            Ok(response) => {
+                *tokens = SessionTokens {
+                    access_token: response.access_token,
+                    client_token: response.client_token,
+                    selected_profile: response
+                        .selected_profile
+                        .or_else(|| tokens.selected_profile.take()),
+                };
+                Ok(SessionStatus::Refreshed)
+            }
+            Err(error) if is_forbidden(&error) => Ok(SessionStatus::ReloginRequired),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Checks whether an error is the server rejecting the tokens.
+///
+/// # Arguments
+///
+/// * `error` - The error returned by the server.
+///
+/// # Returns
+///
+/// `true` if the server answered with a `ForbiddenOperationException`.
+pub(crate) fn is_forbidden(error: &YggdrasilError) -> bool {
+    match error {
+        YggdrasilError::Auth(error) => error.error == "ForbiddenOperationException",
+        _ => false,
+    }
+}
@@ -0,0 +1,623 @@
+use crate::auth_profile::AuthProfile;
+use crate::auth_user::AuthUser;
+use crate::error::YggdrasilError;
+use crate::profile_id::ProfileId;
+use crate::AuthResponse;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The version of the credential file format written by this crate.
+const STORE_FORMAT_VERSION: u32 = 1;
+
+/// The reasons a credential file can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreError {
+    /// The file is not an encrypted credential file.
+    NotEncrypted,
+
+    /// The file was written in an unsupported version of the format.
+    UnsupportedVersion(u32),
+
+    /// The key derivation parameters are out of range.
+    InvalidKdfParams(String),
+
+    /// The file was tampered with, or the passphrase is wrong.
+    Decryption,
+
+    /// The accounts could not be encrypted.
+    Encryption,
+}
+
+impl std::fmt::Display for StoreError {
+    /// Formats the `StoreError` for display.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to write to.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success or failure.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StoreError::NotEncrypted => write!(f, "the file is not an encrypted credential file"),
+            StoreError::UnsupportedVersion(version) => {
+                write!(f, "unsupported credential file version: {}", version)
+            }
+            StoreError::InvalidKdfParams(error) => {
+                write!(f, "invalid key derivation parameters: {}", error)
+            }
+            StoreError::Decryption => {
+                write!(
+                    f,
+                    "the credential file was tampered with or the passphrase is wrong"
+                )
+            }
+            StoreError::Encryption => write!(f, "the credentials could not be encrypted"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+/// An account persisted by a `TokenStore`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoredAccount {
+    /// The base URL of the Yggdrasil server the account belongs to.
+    pub server: String,
+
+    /// The UUID of the profile of the account.
+    #[serde(rename = "profileId")]
+    pub profile_id: ProfileId,
+
+    /// The access token of the account.
+    #[serde(rename = "accessToken")]
+    pub access_token: String,
+
+    /// The client token the access token is bound to.
+    #[serde(rename = "clientToken")]
+    pub client_token: String,
+
+    /// The profile selected by the account, if any.
+    #[serde(rename = "selectedProfile")]
+    pub selected_profile: Option<AuthProfile>,
+
+    /// The user information of the account, if it was requested.
+    pub user: Option<AuthUser>,
+
+    /// The username the account logged in with, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+impl StoredAccount {
+    /// Creates a new `StoredAccount` from the response to an `authenticate` request.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the Yggdrasil server.
+    /// * `response` - The response returned by `authenticate`.
+    ///
+    /// # Returns
+    ///
+    /// The `StoredAccount`, or `None` if the response has no selected profile.
+    pub fn from_auth_response(server: &str, response: &AuthResponse) -> Option<StoredAccount> {
+        let profile = response.selected_profile.as_ref()?;
+        Some(StoredAccount {
+            server: server.to_string(),
+            profile_id: profile.id,
+            access_token: response.access_token.clone(),
+            client_token: response.client_token.clone(),
+            selected_profile: Some(profile.clone()),
+            user: response.user.clone(),
+            username: None,
+        })
+    }
+
+    /// Returns the key identifying the account in a store.
+    ///
+    /// # Returns
+    ///
+    /// The server and the profile UUID.
+    pub(crate) fn key(&self) -> (String, ProfileId) {
+        account_key(&self.server, &self.profile_id)
+    }
+}
+
+/// Persists the accounts of a launcher across restarts.
+///
+/// Accounts are keyed by the base URL of their server and the UUID of their profile, so
+/// the same profile can be stored once per server.
+pub trait TokenStore: Send + Sync {
+    /// Loads the account of a profile on a server.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the Yggdrasil server.
+    /// * `profile_id` - The UUID of the profile.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the account, or `None` if it is not stored.
+    fn load(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+    ) -> Result<Option<StoredAccount>, YggdrasilError>;
+
+    /// Stores an account, replacing the account of the same profile on the same server.
+    ///
+    /// # Arguments
+    ///
+    /// * `account` - The account to store.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    fn save(&self, account: StoredAccount) -> Result<(), YggdrasilError>;
+
+    /// Removes the account of a profile on a server.
+    ///
+    /// Removing the selected account clears the selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the Yggdrasil server.
+    /// * `profile_id` - The UUID of the profile.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    fn remove(&self, server: &str, profile_id: &ProfileId) -> Result<(), YggdrasilError>;
+
+    /// Lists all stored accounts.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the accounts, ordered by server and profile.
+    fn list(&self) -> Result<Vec<StoredAccount>, YggdrasilError>;
+
+    /// Returns which account is selected.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the server and profile UUID of the selected account, or `None` if
+    /// no account is selected.
+    fn selected(&self) -> Result<Option<(String, ProfileId)>, YggdrasilError>;
+
+    /// Records which account is selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `selection` - The server and profile UUID of the account, or `None` to clear the
+    ///   selection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    fn select(&self, selection: Option<(&str, &ProfileId)>) -> Result<(), YggdrasilError>;
+}
+
+/// The accounts held by a store, keyed by server and profile UUID.
+pub(crate) type AccountMap = BTreeMap<(String, ProfileId), StoredAccount>;
+
+/// The accounts held by a store, along with the selected one.
+#[derive(Default, Clone)]
+pub(crate) struct StoreContents {
+    /// The stored accounts.
+    pub(crate) accounts: AccountMap,
+
+    /// The server and profile UUID of the selected account, if any.
+    pub(crate) selected: Option<(String, ProfileId)>,
+}
+
+impl StoreContents {
+    /// Stores an account, replacing the account of the same profile on the same server.
+    ///
+    /// # Arguments
+    ///
+    /// * `account` - The account to store.
+    ///
+    /// # Returns
+    ///
+    /// Always `true`, since the account may have changed.
+    pub(crate) fn save(&mut self, account: StoredAccount) -> bool {
+        self.accounts.insert(account.key(), account);
+        true
+    }
+
+    /// Removes an account, clearing the selection if it was selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the Yggdrasil server.
+    /// * `profile_id` - The UUID of the profile.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the account was stored.
+    pub(crate) fn remove(&mut self, server: &str, profile_id: &ProfileId) -> bool {
+        let key = account_key(server, profile_id);
+        if self.selected.as_ref() == Some(&key) {
+            self.selected = None;
+        }
+        self.accounts.remove(&key).is_some()
+    }
+
+    /// Records which account is selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `selection` - The server and profile UUID of the account, or `None`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the selection changed.
+    pub(crate) fn select(&mut self, selection: Option<(&str, &ProfileId)>) -> bool {
+        let selected = selection.map(|(server, profile_id)| account_key(server, profile_id));
+        if self.selected == selected {
+            return false;
+        }
+        self.selected = selected;
+        true
+    }
+}
+
+/// The contents of a store, shared between threads.
+///
+/// Every change is made to a copy, which is handed to a persistence hook and only replaces
+/// the contents once the hook succeeded, so memory never holds changes that failed to persist.
+#[derive(Default)]
+pub(crate) struct SharedContents {
+    /// The stored accounts and the selection.
+    contents: Mutex<StoreContents>,
+}
+
+impl SharedContents {
+    /// Creates a new `SharedContents` holding the given contents.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The contents loaded by the store.
+    ///
+    /// # Returns
+    ///
+    /// A new `SharedContents` instance.
+    pub(crate) fn new(contents: StoreContents) -> SharedContents {
+        SharedContents {
+            contents: Mutex::new(contents),
+        }
+    }
+
+    /// Loads the account of a profile on a server.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The base URL of the Yggdrasil server.
+    /// * `profile_id` - The UUID of the profile.
+    ///
+    /// # Returns
+    ///
+    /// The account, or `None` if it is not stored.
+    pub(crate) fn load(&self, server: &str, profile_id: &ProfileId) -> Option<StoredAccount> {
+        let contents = self.contents.lock().unwrap();
+        contents
+            .accounts
+            .get(&account_key(server, profile_id))
+            .cloned()
+    }
+
+    /// Lists all stored accounts.
+    ///
+    /// # Returns
+    ///
+    /// The accounts, ordered by server and profile.
+    pub(crate) fn list(&self) -> Vec<StoredAccount> {
+        let contents = self.contents.lock().unwrap();
+        contents.accounts.values().cloned().collect()
+    }
+
+    /// Returns which account is selected.
+    ///
+    /// # Returns
+    ///
+    /// The server and profile UUID of the selected account, or `None`.
+    pub(crate) fn selected(&self) -> Option<(String, ProfileId)> {
+        self.contents.lock().unwrap().selected.clone()
+    }
+
+    /// Applies a change to a copy of the contents, persists the copy if it changed and then
+    /// replaces the contents with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - Applies the change, returning whether the contents changed.
+    /// * `persist` - Persists the changed contents.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()`, or the error of `persist`, in which case the contents are
+    /// left unchanged.
+    pub(crate) fn update(
+        &self,
+        change: impl FnOnce(&mut StoreContents) -> bool,
+        persist: impl FnOnce(&StoreContents) -> Result<(), YggdrasilError>,
+    ) -> Result<(), YggdrasilError> {
+        let mut contents = self.contents.lock().unwrap();
+        let mut updated = contents.clone();
+        if change(&mut updated) {
+            persist(&updated)?;
+            *contents = updated;
+        }
+        Ok(())
+    }
+}
+
+/// The account selected in a credential file.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SelectedAccount {
+    /// The base URL of the server of the account.
+    pub(crate) server: String,
+
+    /// The UUID of the profile of the account.
+    #[serde(rename = "profileId")]
+    pub(crate) profile_id: ProfileId,
+}
+
+/// The document written to credential files.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct StoreDocument {
+    /// The version of the file format.
+    pub(crate) version: u32,
+
+    /// The stored accounts.
+    pub(crate) accounts: Vec<StoredAccount>,
+
+    /// The selected account, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) selected: Option<SelectedAccount>,
+}
+
+impl StoreDocument {
+    /// Creates a new `StoreDocument` holding the given accounts and selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The contents of the store to write.
+    ///
+    /// # Returns
+    ///
+    /// A new `StoreDocument` instance.
+    pub(crate) fn new(contents: &StoreContents) -> StoreDocument {
+        StoreDocument {
+            version: STORE_FORMAT_VERSION,
+            accounts: contents.accounts.values().cloned().collect(),
+            selected: contents
+                .selected
+                .as_ref()
+                .map(|(server, profile_id)| SelectedAccount {
+                    server: server.clone(),
+                    profile_id: *profile_id,
+                }),
+        }
+    }
+
+    /// Converts the document into the contents of a store.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the accounts, keyed by server and profile, and the selection, or
+    /// a `StoreError` if the document was written in another version of the format.
+    pub(crate) fn into_contents(self) -> Result<StoreContents, StoreError> {
+        if self.version != STORE_FORMAT_VERSION {
+            return Err(StoreError::UnsupportedVersion(self.version));
+        }
+
+        Ok(StoreContents {
+            accounts: self
+                .accounts
+                .into_iter()
+                .map(|account| (account.key(), account))
+                .collect(),
+            selected: self
+                .selected
+                .map(|selected| (selected.server, selected.profile_id)),
+        })
+    }
+}
+
+/// Builds the key identifying an account in a store.
+///
+/// # Arguments
+///
+/// * `server` - The base URL of the Yggdrasil server.
+/// * `profile_id` - The UUID of the profile.
+///
+/// # Returns
+///
+/// The server and the profile UUID.
+pub(crate) fn account_key(server: &str, profile_id: &ProfileId) -> (String, ProfileId) {
+    (server.to_string(), *profile_id)
+}
+
+/// Writes a file atomically, readable and writable by its owner only.
+///
+/// The content is written to a temporary file next to the target, flushed to disk and then
+/// renamed over the target, so readers never see a partially written file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file.
+/// * `content` - The content of the file.
+///
+/// # Returns
+///
+/// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+pub(crate) fn write_private_file(path: &Path, content: &[u8]) -> Result<(), YggdrasilError> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&temporary)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+/// A `TokenStore` keeping accounts in memory only.
+#[derive(Default)]
+pub struct MemoryTokenStore {
+    /// The stored accounts and the selection.
+    contents: SharedContents,
+}
+
+impl MemoryTokenStore {
+    /// Creates a new, empty `MemoryTokenStore`.
+    ///
+    /// # Returns
+    ///
+    /// A new `MemoryTokenStore` instance.
+    pub fn new() -> MemoryTokenStore {
+        MemoryTokenStore::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+    ) -> Result<Option<StoredAccount>, YggdrasilError> {
+        Ok(self.contents.load(server, profile_id))
+    }
+
+    fn save(&self, account: StoredAccount) -> Result<(), YggdrasilError> {
+        self.contents
+            .update(|contents| contents.save(account), |_| Ok(()))
+    }
+
+    fn remove(&self, server: &str, profile_id: &ProfileId) -> Result<(), YggdrasilError> {
+        self.contents
+            .update(|contents| contents.remove(server, profile_id), |_| Ok(()))
+    }
+
+    fn list(&self) -> Result<Vec<StoredAccount>, YggdrasilError> {
+        Ok(self.contents.list())
+    }
+
+    fn selected(&self) -> Result<Option<(String, ProfileId)>, YggdrasilError> {
+        Ok(self.contents.selected())
+    }
+
+    fn select(&self, selection: Option<(&str, &ProfileId)>) -> Result<(), YggdrasilError> {
+        self.contents
+            .update(|contents| contents.select(selection), |_| Ok(()))
+    }
+}
+
+/// A `TokenStore` persisting accounts to a JSON file.
+///
+/// The file is rewritten atomically on every change and is only readable and writable by
+/// its owner.
+pub struct JsonFileTokenStore {
+    /// The path of the JSON file.
+    path: PathBuf,
+
+    /// The stored accounts and the selection, mirroring the file.
+    contents: SharedContents,
+}
+
+impl JsonFileTokenStore {
+    /// Opens a `JsonFileTokenStore`, loading the accounts already stored in the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the JSON file, which is created on the first save.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `JsonFileTokenStore` on success or a `YggdrasilError` if the
+    /// file exists but cannot be read or was written in an unsupported format version.
+    pub fn open(path: impl Into<PathBuf>) -> Result<JsonFileTokenStore, YggdrasilError> {
+        let path = path.into();
+        let contents = if path.exists() {
+            let document: StoreDocument = serde_json::from_slice(&fs::read(&path)?)?;
+            document.into_contents()?
+        } else {
+            StoreContents::default()
+        };
+
+        Ok(JsonFileTokenStore {
+            path,
+            contents: SharedContents::new(contents),
+        })
+    }
+
+    /// Writes the accounts and the selection to the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The contents to write.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    fn write(&self, contents: &StoreContents) -> Result<(), YggdrasilError> {
+        let content = serde_json::to_vec_pretty(&StoreDocument::new(contents))?;
+        write_private_file(&self.path, &content)
+    }
+}
+
+impl TokenStore for JsonFileTokenStore {
+    fn load(
+        &self,
+        server: &str,
+        profile_id: &ProfileId,
+    ) -> Result<Option<StoredAccount>, YggdrasilError> {
+        Ok(self.contents.load(server, profile_id))
+    }
+
+    fn save(&self, account: StoredAccount) -> Result<(), YggdrasilError> {
+        self.contents.update(
+            |contents| contents.save(account),
+            |contents| self.write(contents),
+        )
+    }
+
+    fn remove(&self, server: &str, profile_id: &ProfileId) -> Result<(), YggdrasilError> {
+        self.contents.update(
+            |contents| contents.remove(server, profile_id),
+            |contents| self.write(contents),
+        )
+    }
+
+    fn list(&self) -> Result<Vec<StoredAccount>, YggdrasilError> {
+        Ok(self.contents.list())
+    }
+
+    fn selected(&self) -> Result<Option<(String, ProfileId)>, YggdrasilError> {
+        Ok(self.contents.selected())
+    }
+
+    fn select(&self, selection: Option<(&str, &ProfileId)>) -> Result<(), YggdrasilError> {
+        self.contents.update(
+            |contents| contents.select(selection),
+            |contents| self.write(contents),
+        )
+    }
+}
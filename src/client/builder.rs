@@ -0,0 +1,318 @@
+use crate::client::client::{
+    parse_base_url, AuthClient, DEFAULT_PROFILE_BATCH_SIZE, DEFAULT_PROFILE_LOOKUP_CONCURRENCY,
+};
+use crate::crypto::signature::SignatureVerifier;
+use crate::error::YggdrasilError;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, Proxy};
+use std::time::Duration;
+
+/// The User-Agent sent when no custom one is configured.
+const DEFAULT_USER_AGENT: &str = concat!("yggdrasil-authenticator/", env!("CARGO_PKG_VERSION"));
+
+/// The kind of traffic a proxy applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyScheme {
+    /// Proxies only requests to `http://` URLs.
+    Http,
+
+    /// Proxies only requests to `https://` URLs.
+    Https,
+
+    /// Proxies all requests, whatever their scheme.
+    All,
+}
+
+/// A builder for configuring and creating an `AuthClient`.
+///
+/// Proxy URLs may use the `http://`, `https://` or `socks5://` schemes.
+pub struct AuthClientBuilder {
+    /// The base URL for the Yggdrasil authentication server.
+    base_yggdrasil_url: String,
+
+    /// The API root that session, profile, texture and metadata requests are sent below.
+    api_root: Option<String>,
+
+    /// The timeout for establishing a connection.
+    connect_timeout: Option<Duration>,
+
+    /// The timeout for each read from an established connection.
+    read_timeout: Option<Duration>,
+
+    /// The timeout for a whole request, from connecting until the body has been read.
+    timeout: Option<Duration>,
+
+    /// The User-Agent sent with every request.
+    user_agent: String,
+
+    /// Extra headers sent with every request.
+    default_headers: HeaderMap,
+
+    /// The proxies to send requests through, with the traffic they apply to.
+    proxies: Vec<(ProxyScheme, String)>,
+
+    /// Optional username and password used to authenticate against the proxies.
+    proxy_credentials: Option<(String, String)>,
+
+    /// Extra root certificates to trust.
+    root_certificates: Vec<Certificate>,
+
+    /// Whether invalid TLS certificates are accepted.
+    accept_invalid_certs: bool,
+
+    /// The maximum number of names sent in a single batch profile lookup request.
+    profile_batch_size: usize,
+
+    /// The maximum number of batch profile lookup requests in flight at once.
+    profile_lookup_concurrency: usize,
+
+    /// The verifier profile lookups check signatures with, if signatures are enforced.
+    signature_verifier: Option<SignatureVerifier>,
+}
+
+impl AuthClientBuilder {
+    /// Creates a new `AuthClientBuilder` with the default configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_yggdrasil_url` - The base URL for the Yggdrasil authentication server.
+    ///
+    /// # Returns
+    ///
+    /// A new `AuthClientBuilder` instance.
+    pub fn new(base_yggdrasil_url: String) -> AuthClientBuilder {
+        AuthClientBuilder {
+            base_yggdrasil_url,
+            api_root: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            default_headers: HeaderMap::new(),
+            proxies: Vec::new(),
+            proxy_credentials: None,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            profile_batch_size: DEFAULT_PROFILE_BATCH_SIZE,
+            profile_lookup_concurrency: DEFAULT_PROFILE_LOOKUP_CONCURRENCY,
+            signature_verifier: None,
+        }
+    }
+
+    /// Sets the API root that session, profile, texture and metadata requests are sent below.
+    ///
+    /// Authentication requests are still sent below the base URL. Defaults to the base URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_root` - The API root, e.g. `"https://example.com/api/yggdrasil"`.
+    pub fn api_root(mut self, api_root: &str) -> AuthClientBuilder {
+        self.api_root = Some(api_root.to_string());
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The connect timeout.
+    pub fn connect_timeout(mut self, timeout: Duration) -> AuthClientBuilder {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for each read from an established connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The read timeout.
+    pub fn read_timeout(mut self, timeout: Duration) -> AuthClientBuilder {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for a whole request, from connecting until the body has been read.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The total timeout.
+    pub fn timeout(mut self, timeout: Duration) -> AuthClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the User-Agent sent with every request.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_agent` - The User-Agent, e.g. `"MyLauncher/1.2.0"`.
+    pub fn user_agent(mut self, user_agent: &str) -> AuthClientBuilder {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Adds a header sent with every request.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The header name.
+    /// * `value` - The header value.
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> AuthClientBuilder {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Sends requests through a proxy.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - The kind of traffic the proxy applies to.
+    /// * `proxy_url` - The proxy URL, e.g. `"socks5://127.0.0.1:1080"`.
+    pub fn proxy(mut self, scheme: ProxyScheme, proxy_url: &str) -> AuthClientBuilder {
+        self.proxies.push((scheme, proxy_url.to_string()));
+        self
+    }
+
+    /// Sets the credentials used to authenticate against the proxies.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The proxy username.
+    /// * `password` - The proxy password.
+    pub fn proxy_credentials(mut self, username: &str, password: &str) -> AuthClientBuilder {
+        self.proxy_credentials = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    /// Trusts an extra root certificate, e.g. the one of a local test server.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate` - The root certificate to trust.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> AuthClientBuilder {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Controls whether invalid TLS certificates are accepted.
+    ///
+    /// This disables all certificate checks and should only be used against local test servers.
+    ///
+    /// # Arguments
+    ///
+    /// * `accept_invalid_certs` - Whether invalid certificates are accepted.
+    pub fn danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> AuthClientBuilder {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    /// Sets the maximum number of names sent in a single batch profile lookup request.
+    ///
+    /// Defaults to 10, the limit of the Mojang API.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - The maximum number of names per request.
+    pub fn profile_batch_size(mut self, batch_size: usize) -> AuthClientBuilder {
+        self.profile_batch_size = batch_size;
+        self
+    }
+
+    /// Sets the maximum number of batch profile lookup requests in flight at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `concurrency` - The maximum number of concurrent requests.
+    pub fn profile_lookup_concurrency(mut self, concurrency: usize) -> AuthClientBuilder {
+        self.profile_lookup_concurrency = concurrency;
+        self
+    }
+
+    /// Enforces signatures on the profiles returned by profile lookups.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature_verifier` - The verifier to check signatures with.
+    pub fn signature_verifier(
+        mut self,
+        signature_verifier: SignatureVerifier,
+    ) -> AuthClientBuilder {
+        self.signature_verifier = Some(signature_verifier);
+        self
+    }
+
+    /// Builds the `AuthClient` with the configured options.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `AuthClient` on success or a `YggdrasilError` if the base URL is
+    /// invalid, or a proxy or the HTTP client could not be configured.
+    pub fn build(self) -> Result<AuthClient, YggdrasilError> {
+        // Reject a malformed base URL or API root before configuring anything else
+        parse_base_url(&self.base_yggdrasil_url)?;
+        let api_root = self.api_root.as_deref().map(parse_base_url).transpose()?;
+
+        let mut builder = Client::builder()
+            .user_agent(self.user_agent)
+            .default_headers(self.default_headers)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        for (scheme, proxy_url) in &self.proxies {
+            let mut proxy = match scheme {
+                ProxyScheme::Http => Proxy::http(proxy_url),
+                ProxyScheme::Https => Proxy::https(proxy_url),
+                ProxyScheme::All => Proxy::all(proxy_url),
+            }
+            .map_err(YggdrasilError::Proxy)?;
+            if let Some((username, password)) = &self.proxy_credentials {
+                proxy = proxy.basic_auth(username, password);
+            }
+            builder = builder.proxy(proxy);
+        }
+
+        for certificate in self.root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        let http_client = builder.build()?;
+        let mut client = AuthClient::with_client(self.base_yggdrasil_url, http_client)?;
+        client.set_profile_lookup_limits(self.profile_batch_size, self.profile_lookup_concurrency);
+        client.set_signature_verifier(self.signature_verifier);
+        if let Some(api_root) = api_root {
+            client.set_api_root(api_root);
+        }
+        Ok(client)
+    }
+
+    /// Builds the `AuthClient`, treating the base URL as a user-entered address to resolve.
+    ///
+    /// `https://` is assumed when the URL has no scheme, and the API root is resolved by
+    /// following the authlib-injector API Location Indication header once. The base URL of the
+    /// client is then the `authserver/` endpoints below the API root.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `AuthClient` using the resolved API root on success or a
+    /// `YggdrasilError` on failure.
+    pub async fn discover(mut self) -> Result<AuthClient, YggdrasilError> {
+        let url = self.base_yggdrasil_url.trim();
+        let url = if url.contains("://") {
+            url.to_string()
+        } else {
+            format!("https://{}", url)
+        };
+
+        self.base_yggdrasil_url = url.clone();
+        self.build()?.resolve_api_location(&url).await
+    }
+}
@@ -1,22 +1,56 @@
+use crate::api_metadata::ApiMetadata;
 use crate::auth_agent::AuthAgent;
 use crate::auth_error::AuthError;
 use crate::auth_profile::AuthProfile;
+use crate::client::builder::{AuthClientBuilder, ProxyScheme};
+use crate::client::profile_lookup::ProfileLookup;
+use crate::crypto::signature::SignatureVerifier;
+use crate::error::YggdrasilError;
+use crate::game_profile::GameProfile;
+use crate::profile_id::ProfileId;
+use crate::texture::skin_image::validate_texture;
+use crate::textures_payload::{SkinModel, TextureType};
 use crate::{
-    AuthRequest, AuthResponse, InvalidateRequest, RefreshRequest, RefreshResponse, SignoutRequest,
-    ValidateRequest,
+    AuthRequest, AuthResponse, InvalidateRequest, JoinRequest, RefreshRequest, RefreshResponse,
+    SignoutRequest, ValidateRequest,
 };
-use reqwest::{Client, Proxy};
-use std::error::Error;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Response};
+use std::collections::HashMap;
 use std::str;
+use url::Url;
+
+/// The number of names the Mojang API accepts in a single batch profile lookup request.
+pub(crate) const DEFAULT_PROFILE_BATCH_SIZE: usize = 10;
+
+/// The number of batch profile lookup requests sent concurrently by default.
+pub(crate) const DEFAULT_PROFILE_LOOKUP_CONCURRENCY: usize = 4;
+
+/// The response header used by authlib-injector to indicate the API root.
+const API_LOCATION_HEADER: &str = "X-Authlib-Injector-API-Location";
 
 /// A client for authenticating, refreshing, validating, invalidating, and signing out
 /// through Yggdrasil's authentication API.
 pub struct AuthClient {
-    /// The base URL for the Yggdrasil authentication server.
-    base_yggdrasil_url: String,
+    /// The base URL for the Yggdrasil authentication server, always ending with a slash.
+    base_url: Url,
+
+    /// The API root that session, profile, texture and metadata requests are sent below,
+    /// always ending with a slash. Defaults to the base URL.
+    api_root: Url,
 
-    /// An optional proxy URL for sending requests through a proxy server.
-    proxy_url: Option<String>,
+    /// The HTTP client used for every request, sharing one connection pool.
+    http_client: Client,
+
+    /// The maximum number of names sent in a single batch profile lookup request.
+    profile_batch_size: usize,
+
+    /// The maximum number of batch profile lookup requests in flight at once.
+    profile_lookup_concurrency: usize,
+
+    /// The verifier profile lookups check signatures with, if signatures are enforced.
+    signature_verifier: Option<SignatureVerifier>,
 }
 
 impl AuthClient {
@@ -29,21 +63,191 @@ impl AuthClient {
     ///
     /// # Returns
     ///
-    /// An initialized `AuthClient`.
-    pub fn new(base_yggdrasil_url: String, proxy_url: Option<String>) -> AuthClient {
-        AuthClient {
-            base_yggdrasil_url,
-            proxy_url,
+    /// A `Result` containing the initialized `AuthClient` on success or a `YggdrasilError`
+    /// if the base URL or the proxy URL is invalid.
+    pub fn new(
+        base_yggdrasil_url: String,
+        proxy_url: Option<String>,
+    ) -> Result<AuthClient, YggdrasilError> {
+        let mut builder = AuthClientBuilder::new(base_yggdrasil_url);
+        if let Some(proxy_url) = proxy_url {
+            builder = builder.proxy(ProxyScheme::Http, &proxy_url);
         }
+        builder.build()
+    }
+
+    /// Creates a new `AuthClientBuilder` for configuring timeouts, headers, proxies and TLS.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_yggdrasil_url` - The base URL for the Yggdrasil authentication server.
+    ///
+    /// # Returns
+    ///
+    /// A new `AuthClientBuilder` instance.
+    pub fn builder(base_yggdrasil_url: String) -> AuthClientBuilder {
+        AuthClientBuilder::new(base_yggdrasil_url)
+    }
+
+    /// Creates a new `AuthClient` that sends its requests through a caller-supplied HTTP client.
+    ///
+    /// This allows the connection pool of the `reqwest::Client` to be shared with the rest of
+    /// an application. The User-Agent, timeouts and proxies of the supplied client are used as-is.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_yggdrasil_url` - The base URL for the Yggdrasil authentication server.
+    /// * `http_client` - The HTTP client used for every request.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the initialized `AuthClient` on success or a `YggdrasilError`
+    /// if the base URL is invalid.
+    pub fn with_client(
+        base_yggdrasil_url: String,
+        http_client: Client,
+    ) -> Result<AuthClient, YggdrasilError> {
+        let base_url = parse_base_url(&base_yggdrasil_url)?;
+        Ok(AuthClient {
+            api_root: base_url.clone(),
+            base_url,
+            http_client,
+            profile_batch_size: DEFAULT_PROFILE_BATCH_SIZE,
+            profile_lookup_concurrency: DEFAULT_PROFILE_LOOKUP_CONCURRENCY,
+            signature_verifier: None,
+        })
+    }
+
+    /// Sets the limits applied to batch profile lookups.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - The maximum number of names sent in a single request.
+    /// * `concurrency` - The maximum number of requests in flight at once.
+    pub(crate) fn set_profile_lookup_limits(&mut self, batch_size: usize, concurrency: usize) {
+        self.profile_batch_size = batch_size.max(1);
+        self.profile_lookup_concurrency = concurrency.max(1);
+    }
+
+    /// Creates a new `AuthClient` by resolving the API root from a URL entered by a user.
+    ///
+    /// The URL may omit its scheme, in which case `https://` is assumed. If the server answers
+    /// with an `X-Authlib-Injector-API-Location` header, the API root it indicates is used;
+    /// otherwise the URL itself is the API root.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to resolve, e.g. `"littleskin.cn"`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an `AuthClient` using the resolved API root, which is exposed by
+    /// `api_root`. Its `base_url` points at the `authserver/` endpoints below it.
+    pub async fn discover(url: &str) -> Result<AuthClient, YggdrasilError> {
+        AuthClientBuilder::new(url.to_string()).discover().await
+    }
+
+    /// Resolves the API root with the authlib-injector API Location Indication (ALI).
+    ///
+    /// The base URL is requested once and the ALI header, which may be absolute or relative,
+    /// is followed at most once.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL entered by the user, with its scheme.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing this client, with its API root set to the resolved URL and its base
+    /// URL set to the `authserver/` endpoints below it.
+    pub(crate) async fn resolve_api_location(
+        mut self,
+        url: &str,
+    ) -> Result<AuthClient, YggdrasilError> {
+        let res = self.http_client.get(url).send().await?;
+
+        let location = res
+            .headers()
+            .get(API_LOCATION_HEADER)
+            .and_then(|value| value.to_str().ok());
+        let resolved = match location {
+            Some(location) => res.url().join(location)?,
+            None => res.url().clone(),
+        };
+
+        // authlib-injector serves the authentication endpoints below the API root
+        self.api_root = parse_base_url(resolved.as_str())?;
+        self.base_url = self.api_root.join("authserver/")?;
+        Ok(self)
+    }
+
+    /// Enforces signatures on the profiles returned by `profile_by_uuid` and `has_joined`.
+    ///
+    /// While a verifier is set, signed properties are always requested and lookups fail with
+    /// `YggdrasilError::InvalidSignature` if any property is unsigned or forged.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature_verifier` - The verifier to check signatures with, or `None` to stop
+    ///   enforcing signatures.
+    pub fn set_signature_verifier(&mut self, signature_verifier: Option<SignatureVerifier>) {
+        self.signature_verifier = signature_verifier;
+    }
+
+    /// Returns the normalised base URL of the Yggdrasil authentication server.
+    ///
+    /// # Returns
+    ///
+    /// The base URL, always ending with a slash.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Sets the API root that session, profile, texture and metadata requests are sent below.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_root` - The normalised API root.
+    pub(crate) fn set_api_root(&mut self, api_root: Url) {
+        self.api_root = api_root;
+    }
+
+    /// Returns the normalised API root of the Yggdrasil server.
+    ///
+    /// Session, profile, texture and metadata requests are sent below it. Unless one was
+    /// configured or discovered, it is the base URL.
+    ///
+    /// # Returns
+    ///
+    /// The API root, always ending with a slash.
+    pub fn api_root(&self) -> &Url {
+        &self.api_root
     }
 
-    /// Returns the proxy URL as an `Option<&str>`.
+    /// Resolves an authentication endpoint path against the base URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The endpoint path, relative to the base URL and without a leading slash.
     ///
     /// # Returns
     ///
-    /// An optional string slice representing the proxy URL, or `None` if no proxy URL is set.
-    fn get_proxy(&self) -> Option<&str> {
-        self.proxy_url.as_deref()
+    /// A `Result` containing the absolute URL of the endpoint.
+    fn endpoint(&self, endpoint: &str) -> Result<Url, YggdrasilError> {
+        Ok(self.base_url.join(endpoint)?)
+    }
+
+    /// Resolves an endpoint path against the API root.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The endpoint path, relative to the API root and without a leading slash.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the absolute URL of the endpoint.
+    fn api_endpoint(&self, endpoint: &str) -> Result<Url, YggdrasilError> {
+        Ok(self.api_root.join(endpoint)?)
     }
 
     /// Authenticates the user with the Yggdrasil authentication server.
@@ -58,7 +262,7 @@ impl AuthClient {
     ///
     /// # Returns
     ///
-    /// A `Result` containing the `AuthResponse` on success or an `Error` on failure.
+    /// A `Result` containing the `AuthResponse` on success or a `YggdrasilError` on failure.
     pub async fn authenticate(
         &self,
         agent: AuthAgent,
@@ -66,7 +270,7 @@ impl AuthClient {
         password: &str,
         client_token: &str,
         request_user: bool,
-    ) -> Result<AuthResponse, Box<dyn Error>> {
+    ) -> Result<AuthResponse, YggdrasilError> {
         let content = serde_json::to_string(&AuthRequest::new(
             agent,
             username.to_string(),
@@ -74,11 +278,9 @@ impl AuthClient {
             client_token.to_string(),
             request_user,
         ))?;
-        let result = send_post_request(
-            &format!("{}/authenticate", self.base_yggdrasil_url),
-            &content,
-            self.get_proxy(),
-        ).await?;
+        let result = self
+            .send_post_request(self.endpoint("authenticate")?, &content)
+            .await?;
         let response: AuthResponse = serde_json::from_str(result.as_deref().unwrap_or(""))?;
         Ok(response)
     }
@@ -94,25 +296,23 @@ impl AuthClient {
     ///
     /// # Returns
     ///
-    /// A `Result` containing the `RefreshResponse` on success or an `Error` on failure.
+    /// A `Result` containing the `RefreshResponse` on success or a `YggdrasilError` on failure.
     pub async fn refresh(
         &self,
         access_token: &str,
         client_token: &str,
         request_user: bool,
         selected_profile: Option<AuthProfile>,
-    ) -> Result<RefreshResponse, Box<dyn Error>> {
+    ) -> Result<RefreshResponse, YggdrasilError> {
         let content = serde_json::to_string(&RefreshRequest::new(
             access_token.to_string(),
             client_token.to_string(),
             request_user,
             selected_profile,
         ))?;
-        let result = send_post_request(
-            &format!("{}/refresh", self.base_yggdrasil_url),
-            &content,
-            self.get_proxy(),
-        ).await?;
+        let result = self
+            .send_post_request(self.endpoint("refresh")?, &content)
+            .await?;
         let response: RefreshResponse = serde_json::from_str(result.as_deref().unwrap_or(""))?;
         Ok(response)
     }
@@ -125,14 +325,11 @@ impl AuthClient {
     ///
     /// # Returns
     ///
-    /// A `Result` containing `()` on success or an `Error` on failure.
-    pub async fn validate(&self, access_token: &str) -> Result<(), Box<dyn Error>> {
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    pub async fn validate(&self, access_token: &str) -> Result<(), YggdrasilError> {
         let content = serde_json::to_string(&ValidateRequest::new(access_token.to_string()))?;
-        send_post_request(
-            &format!("{}/validate", self.base_yggdrasil_url),
-            &content,
-            self.get_proxy(),
-        ).await?;
+        self.send_post_request(self.endpoint("validate")?, &content)
+            .await?;
         Ok(())
     }
 
@@ -145,21 +342,18 @@ impl AuthClient {
     ///
     /// # Returns
     ///
-    /// A `Result` containing `()` on success or an `Error` on failure.
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
     pub async fn invalidate(
         &self,
         access_token: &str,
         client_token: &str,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), YggdrasilError> {
         let content = serde_json::to_string(&InvalidateRequest::new(
             access_token.to_string(),
             client_token.to_string(),
         ))?;
-        send_post_request(
-            &format!("{}/invalidate", self.base_yggdrasil_url),
-            &content,
-            self.get_proxy(),
-        ).await?;
+        self.send_post_request(self.endpoint("invalidate")?, &content)
+            .await?;
         Ok(())
     }
 
@@ -172,43 +366,374 @@ impl AuthClient {
     ///
     /// # Returns
     ///
-    /// A `Result` containing `()` on success or an `Error` on failure.
-    pub async fn signout(&self, username: &str, password: &str) -> Result<(), Box<dyn Error>> {
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    pub async fn signout(&self, username: &str, password: &str) -> Result<(), YggdrasilError> {
         let content = serde_json::to_string(&SignoutRequest::new(
             username.to_string(),
             password.to_string(),
         ))?;
-        send_post_request(
-            &format!("{}/signout", self.base_yggdrasil_url),
-            &content,
-            self.get_proxy(),
-        ).await?;
+        self.send_post_request(self.endpoint("signout")?, &content)
+            .await?;
         Ok(())
     }
-}
 
-async fn send_post_request(
-    url: &str,
-    json: &str,
-    proxy: Option<&str>,
-) -> Result<Option<String>, Box<dyn Error>> {
-    // Build the client, with or without proxy
-    let client = if let Some(proxy_url) = proxy {
-        let proxy = Proxy::http(proxy_url)?;
-        Client::builder().proxy(proxy).build()?
-    } else {
-        Client::new()
-    };
-
-    let res = client
-        .post(url)
-        .header("User-Agent", "yggdrasil-authenticator/0.1.0")
-        .header("Accept-Charset", "UTF-8")
-        .header("Content-Type", "application/json;charset=utf-8")
-        .body(json.to_string())
-        .send()
+    /// Tells the session server that the user is joining an online-mode server.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the user.
+    /// * `selected_profile` - The UUID of the profile joining the server.
+    /// * `server_id` - The server ID hash computed during the handshake.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure.
+    pub async fn join(
+        &self,
+        access_token: &str,
+        selected_profile: &ProfileId,
+        server_id: &str,
+    ) -> Result<(), YggdrasilError> {
+        let content = serde_json::to_string(&JoinRequest::new(
+            access_token.to_string(),
+            *selected_profile,
+            server_id.to_string(),
+        ))?;
+        self.send_post_request(
+            self.api_endpoint("sessionserver/session/minecraft/join")?,
+            &content,
+        )
         .await?;
+        Ok(())
+    }
+
+    /// Asks the session server whether a user has joined an online-mode server.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The name of the profile that is connecting.
+    /// * `server_id` - The server ID hash computed during the handshake.
+    /// * `ip` - The IP address of the connecting client, if it should be checked.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the joined `GameProfile`, or `None` if the user has not joined.
+    pub async fn has_joined(
+        &self,
+        username: &str,
+        server_id: &str,
+        ip: Option<&str>,
+    ) -> Result<Option<GameProfile>, YggdrasilError> {
+        let mut query = vec![("username", username), ("serverId", server_id)];
+        if let Some(ip) = ip {
+            query.push(("ip", ip));
+        }
 
+        let result = self
+            .send_get_request(
+                self.api_endpoint("sessionserver/session/minecraft/hasJoined")?,
+                &query,
+            )
+            .await?;
+        self.read_game_profile(result)
+    }
+
+    /// Looks up a game profile, including its properties, by its UUID.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The UUID of the profile.
+    /// * `signed` - Whether the server should sign the properties of the profile. Signed
+    ///   properties are always requested while a signature verifier is set.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `GameProfile`, or `None` if no profile has this UUID.
+    pub async fn profile_by_uuid(
+        &self,
+        uuid: &ProfileId,
+        signed: bool,
+    ) -> Result<Option<GameProfile>, YggdrasilError> {
+        let signed = signed || self.signature_verifier.is_some();
+        let unsigned = if signed { "false" } else { "true" };
+        let result = self
+            .send_get_request(
+                self.api_endpoint(&format!("sessionserver/session/minecraft/profile/{}", uuid))?,
+                &[("unsigned", unsigned)],
+            )
+            .await?;
+        self.read_game_profile(result)
+    }
+
+    /// Decodes a game profile returned by the session server, verifying its signatures if a
+    /// signature verifier is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The response body, or `None` if the server found no profile.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `GameProfile`, or `None` if the server found no profile.
+    fn read_game_profile(
+        &self,
+        result: Option<String>,
+    ) -> Result<Option<GameProfile>, YggdrasilError> {
+        let Some(body) = result else {
+            return Ok(None);
+        };
+
+        let profile: GameProfile = serde_json::from_str(&body)?;
+        if let Some(verifier) = &self.signature_verifier {
+            verifier.verify_profile(&profile)?;
+        }
+        Ok(Some(profile))
+    }
+
+    /// Looks up the profiles with the given names.
+    ///
+    /// Names are de-duplicated case-insensitively, keeping the first spelling, split into
+    /// batches of the configured size and looked up with bounded concurrency.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names of the profiles to look up.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ProfileLookup` with the found profiles keyed by the requested
+    /// name, and the names that did not resolve.
+    pub async fn profiles_by_names(&self, names: &[&str]) -> Result<ProfileLookup, YggdrasilError> {
+        // Keep the first spelling of every name, keyed by its lowercase form
+        let mut requested: HashMap<String, String> = HashMap::new();
+        let mut unique = Vec::new();
+        for name in names {
+            let key = name.to_lowercase();
+            if !name.is_empty() && !requested.contains_key(&key) {
+                requested.insert(key, name.to_string());
+                unique.push(*name);
+            }
+        }
+
+        let batches: Vec<Vec<AuthProfile>> = stream::iter(unique.chunks(self.profile_batch_size))
+            .map(|chunk| self.profiles_by_names_batch(chunk))
+            .buffer_unordered(self.profile_lookup_concurrency)
+            .try_collect()
+            .await?;
+
+        let mut profiles = HashMap::new();
+        for profile in batches.into_iter().flatten() {
+            if let Some(name) = requested.get(&profile.name.to_lowercase()) {
+                profiles.insert(name.clone(), profile);
+            }
+        }
+
+        let missing = unique
+            .iter()
+            .filter(|name| !profiles.contains_key(**name))
+            .map(|name| name.to_string())
+            .collect();
+
+        Ok(ProfileLookup { profiles, missing })
+    }
+
+    /// Looks up a single batch of profiles by their names.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names of the profiles to look up, within the server's batch limit.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the profiles that were found.
+    async fn profiles_by_names_batch(
+        &self,
+        names: &[&str],
+    ) -> Result<Vec<AuthProfile>, YggdrasilError> {
+        let content = serde_json::to_string(names)?;
+        let result = self
+            .send_post_request(self.api_endpoint("api/profiles/minecraft")?, &content)
+            .await?;
+        match result {
+            Some(body) => Ok(serde_json::from_str(&body)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Uploads a texture for a profile.
+    ///
+    /// The image is validated and normalised with `validate_texture` first, so malformed files
+    /// fail locally with an explanation instead of being rejected by the server. The given
+    /// `model` is always sent, whatever model `validate_texture` detects in the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the user owning the profile.
+    /// * `uuid` - The UUID of the profile.
+    /// * `texture_type` - The type of the texture to upload.
+    /// * `model` - The model of the skin; ignored for capes.
+    /// * `png` - The PNG image of the texture.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure, such as
+    /// `InvalidTexture`, `Unauthorized`, `Forbidden` or `PayloadTooLarge`.
+    pub async fn upload_texture(
+        &self,
+        access_token: &str,
+        uuid: &ProfileId,
+        texture_type: TextureType,
+        model: SkinModel,
+        png: Vec<u8>,
+    ) -> Result<(), YggdrasilError> {
+        let texture = validate_texture(texture_type, &png)?;
+        let model = match (texture_type, model) {
+            (TextureType::Skin, SkinModel::Slim) => "slim",
+            _ => "",
+        };
+        let file = Part::bytes(texture.png)
+            .file_name(format!("{}.png", texture_type.as_str()))
+            .mime_str("image/png")?;
+        let form = Form::new().text("model", model).part("file", file);
+
+        let res = self
+            .http_client
+            .put(self.texture_endpoint(uuid, texture_type)?)
+            .bearer_auth(access_token)
+            .multipart(form)
+            .send()
+            .await?;
+
+        read_bearer_response(res).await?;
+        Ok(())
+    }
+
+    /// Deletes a texture of a profile, resetting it to the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the user owning the profile.
+    /// * `uuid` - The UUID of the profile.
+    /// * `texture_type` - The type of the texture to delete.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` on success or a `YggdrasilError` on failure, such as
+    /// `Unauthorized` or `Forbidden`.
+    pub async fn delete_texture(
+        &self,
+        access_token: &str,
+        uuid: &ProfileId,
+        texture_type: TextureType,
+    ) -> Result<(), YggdrasilError> {
+        let res = self
+            .http_client
+            .delete(self.texture_endpoint(uuid, texture_type)?)
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+
+        read_bearer_response(res).await?;
+        Ok(())
+    }
+
+    /// Resolves the endpoint used to upload and delete a texture of a profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The UUID of the profile.
+    /// * `texture_type` - The type of the texture.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the absolute URL of the endpoint.
+    fn texture_endpoint(
+        &self,
+        uuid: &ProfileId,
+        texture_type: TextureType,
+    ) -> Result<Url, YggdrasilError> {
+        self.api_endpoint(&format!(
+            "api/user/profile/{}/{}",
+            uuid,
+            texture_type.as_str()
+        ))
+    }
+
+    /// Fetches the metadata document served at the API root.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ApiMetadata` on success or a `YggdrasilError` on failure.
+    pub async fn metadata(&self) -> Result<ApiMetadata, YggdrasilError> {
+        let result = self.send_get_request(self.api_root.clone(), &[]).await?;
+        let response: ApiMetadata = serde_json::from_str(result.as_deref().unwrap_or(""))?;
+        Ok(response)
+    }
+
+    /// Sends a JSON `POST` request to the given endpoint of the Yggdrasil server.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The absolute URL of the endpoint.
+    /// * `json` - The JSON request body.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response body, or `None` for `204 No Content` responses.
+    async fn send_post_request(
+        &self,
+        url: Url,
+        json: &str,
+    ) -> Result<Option<String>, YggdrasilError> {
+        let res = self
+            .http_client
+            .post(url)
+            .header("Accept-Charset", "UTF-8")
+            .header("Content-Type", "application/json;charset=utf-8")
+            .body(json.to_string())
+            .send()
+            .await?;
+
+        read_response(res).await
+    }
+
+    /// Sends a `GET` request to the given endpoint of the Yggdrasil server.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The absolute URL of the endpoint.
+    /// * `query` - The query parameters to append to the URL.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response body, or `None` for `204 No Content` responses.
+    async fn send_get_request(
+        &self,
+        url: Url,
+        query: &[(&str, &str)],
+    ) -> Result<Option<String>, YggdrasilError> {
+        let res = self
+            .http_client
+            .get(url)
+            .query(query)
+            .header("Accept-Charset", "UTF-8")
+            .send()
+            .await?;
+
+        read_response(res).await
+    }
+}
+
+/// Reads the body of a response from the Yggdrasil server, turning error statuses into errors.
+///
+/// # Arguments
+///
+/// * `res` - The response to read.
+///
+/// # Returns
+///
+/// A `Result` containing the response body, or `None` for `204 No Content` responses.
+async fn read_response(res: Response) -> Result<Option<String>, YggdrasilError> {
     let status = res.status();
 
     // Validate, invalidate and sign out operations respond with this status
@@ -228,14 +753,70 @@ async fn send_post_request(
         // Handle this error if possible
         let error: Result<AuthError, serde_json::Error> = serde_json::from_str(&response);
         return match error {
-            Ok(auth_error) => {
-                Err(auth_error.into())
-            }
-            Err(_error) => {
-                Err(format!("server status: {}, response: {}", status, response).into())
-            }
-        }
+            Ok(auth_error) => Err(YggdrasilError::Auth(auth_error)),
+            Err(_error) => Err(YggdrasilError::Server {
+                status,
+                body: response,
+            }),
+        };
     }
 
     Ok(Some(response))
 }
+
+/// Reads the body of a response to a request authenticated with a bearer token.
+///
+/// Rejections of the token or of the request are turned into dedicated errors.
+///
+/// # Arguments
+///
+/// * `res` - The response to read.
+///
+/// # Returns
+///
+/// A `Result` containing the response body, or `None` for `204 No Content` responses.
+async fn read_bearer_response(res: Response) -> Result<Option<String>, YggdrasilError> {
+    let status = res.status();
+    if status == reqwest::StatusCode::PAYLOAD_TOO_LARGE {
+        return Err(YggdrasilError::PayloadTooLarge);
+    }
+    if status != reqwest::StatusCode::UNAUTHORIZED && status != reqwest::StatusCode::FORBIDDEN {
+        return read_response(res).await;
+    }
+
+    let body = res.bytes().await?;
+    let error: Option<AuthError> = serde_json::from_slice(&body).ok();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        Err(YggdrasilError::Unauthorized(error))
+    } else {
+        Err(YggdrasilError::Forbidden(error))
+    }
+}
+
+/// Parses and normalises the base URL of a Yggdrasil server.
+///
+/// Query and fragment are dropped and the path is made to end with exactly one slash, so that
+/// endpoint paths can be joined onto it whether or not the caller added a trailing slash.
+///
+/// # Arguments
+///
+/// * `base_yggdrasil_url` - The base URL to parse.
+///
+/// # Returns
+///
+/// A `Result` containing the normalised URL, or a `YggdrasilError` if the URL is malformed or
+/// does not use the `http` or `https` scheme.
+pub(crate) fn parse_base_url(base_yggdrasil_url: &str) -> Result<Url, YggdrasilError> {
+    let mut url = Url::parse(base_yggdrasil_url.trim())?;
+
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(YggdrasilError::UnsupportedScheme(url.scheme().to_string()));
+    }
+
+    let path = format!("{}/", url.path().trim_end_matches('/'));
+    url.set_path(&path);
+    url.set_query(None);
+    url.set_fragment(None);
+
+    Ok(url)
+}
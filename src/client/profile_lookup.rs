@@ -0,0 +1,12 @@
+use crate::auth_profile::AuthProfile;
+use std::collections::HashMap;
+
+/// The result of looking up several profiles by their names.
+#[derive(Debug, Clone)]
+pub struct ProfileLookup {
+    /// The profiles that were found, keyed by the name as it was requested.
+    pub profiles: HashMap<String, AuthProfile>,
+
+    /// The requested names that did not resolve to a profile.
+    pub missing: Vec<String>,
+}
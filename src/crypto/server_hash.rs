@@ -0,0 +1,57 @@
+use sha1::{Digest, Sha1};
+
+/// Computes the server ID hash sent to the session server's `join` and `hasJoined` endpoints.
+///
+/// Minecraft hashes the server ID, the shared secret and the server's public key with SHA-1
+/// and prints the digest as a signed two's-complement number in hexadecimal, without leading
+/// zeros and with a `-` prefix when negative.
+///
+/// # Arguments
+///
+/// * `server_id` - The server ID sent by the server, usually empty.
+/// * `shared_secret` - The shared secret generated by the client.
+/// * `public_key_der` - The DER-encoded public key sent by the server.
+///
+/// # Returns
+///
+/// The server ID hash, e.g. `"-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1"`.
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key_der: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(server_id.as_bytes());
+    hasher.update(shared_secret);
+    hasher.update(public_key_der);
+    let mut digest: [u8; 20] = hasher.finalize().into();
+
+    // The digest is read as a big-endian two's-complement number
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        negate(&mut digest);
+    }
+
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let hex = hex.trim_start_matches('0');
+    let hex = if hex.is_empty() { "0" } else { hex };
+
+    if negative {
+        format!("-{}", hex)
+    } else {
+        hex.to_string()
+    }
+}
+
+/// Negates a big-endian two's-complement number in place.
+///
+/// # Arguments
+///
+/// * `bytes` - The number to negate.
+fn negate(bytes: &mut [u8]) {
+    let mut carry = true;
+    for byte in bytes.iter_mut().rev() {
+        *byte = !*byte;
+        if carry {
+            let (sum, overflow) = byte.overflowing_add(1);
+            *byte = sum;
+            carry = overflow;
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::api_metadata::ApiMetadata;
+use crate::error::YggdrasilError;
+use crate::game_profile::{GameProfile, ProfileProperty};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::signature::Verifier;
+use rsa::RsaPublicKey;
+use sha1::Sha1;
+
+/// The outcome of verifying the signature of a profile property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureCheck {
+    /// The property is signed and the signature matches its value.
+    Valid,
+
+    /// The property is signed but the signature does not match its value.
+    Invalid,
+
+    /// The property carries no signature.
+    Unsigned,
+}
+
+/// Verifies the signatures Yggdrasil servers put on profile properties.
+///
+/// Properties are signed with SHA1withRSA using the private key matching the
+/// `signaturePublickey` published in the server's metadata.
+#[derive(Clone)]
+pub struct SignatureVerifier {
+    /// The key used to verify signatures.
+    key: VerifyingKey<Sha1>,
+}
+
+impl SignatureVerifier {
+    /// Creates a new `SignatureVerifier` from a PEM-encoded public key.
+    ///
+    /// # Arguments
+    ///
+    /// * `pem` - The public key, e.g. `"-----BEGIN PUBLIC KEY-----\n..."`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SignatureVerifier` on success or a `YggdrasilError` if the key
+    /// cannot be parsed.
+    pub fn from_pem(pem: &str) -> Result<SignatureVerifier, YggdrasilError> {
+        let key = RsaPublicKey::from_public_key_pem(pem.trim())
+            .map_err(|error| YggdrasilError::InvalidPublicKey(error.to_string()))?;
+        Ok(SignatureVerifier {
+            key: VerifyingKey::new(key),
+        })
+    }
+
+    /// Creates a new `SignatureVerifier` from the public key published in a server's metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - The metadata fetched from the server.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SignatureVerifier` on success or a `YggdrasilError` if the
+    /// server publishes no key or the key cannot be parsed.
+    pub fn from_metadata(metadata: &ApiMetadata) -> Result<SignatureVerifier, YggdrasilError> {
+        let pem = metadata
+            .signature_publickey
+            .as_deref()
+            .ok_or(YggdrasilError::MissingPublicKey)?;
+        SignatureVerifier::from_pem(pem)
+    }
+
+    /// Verifies the signature of a profile property.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The property to verify.
+    ///
+    /// # Returns
+    ///
+    /// The `SignatureCheck` describing whether the property is signed and the signature matches.
+    pub fn verify_property(&self, property: &ProfileProperty) -> SignatureCheck {
+        let Some(signature) = &property.signature else {
+            return SignatureCheck::Unsigned;
+        };
+
+        let signature = STANDARD
+            .decode(signature)
+            .ok()
+            .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok());
+        let Some(signature) = signature else {
+            return SignatureCheck::Invalid;
+        };
+
+        match self.key.verify(property.value.as_bytes(), &signature) {
+            Ok(()) => SignatureCheck::Valid,
+            Err(_) => SignatureCheck::Invalid,
+        }
+    }
+
+    /// Verifies the signatures of all properties of a game profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The profile to verify.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` if every property carries a valid signature, or a
+    /// `YggdrasilError` naming the first property that does not.
+    pub fn verify_profile(&self, profile: &GameProfile) -> Result<(), YggdrasilError> {
+        for property in &profile.properties {
+            if self.verify_property(property) != SignatureCheck::Valid {
+                return Err(YggdrasilError::InvalidSignature(property.name.clone()));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use crate::account::token_store::StoreError;
+use crate::auth_error::AuthError;
+use crate::microsoft::model::OAuthError;
+use crate::microsoft::xbox_error::XboxError;
+use crate::profile_id::ProfileId;
+use crate::texture::skin_image::TextureImageError;
+use reqwest::StatusCode;
+
+/// The error type returned by every operation of this crate.
+///
+/// Each variant describes one way a call to the Yggdrasil API can fail, so callers can
+/// match on the failure instead of downcasting a boxed error.
+#[derive(Debug)]
+pub enum YggdrasilError {
+    /// The HTTP request could not be sent or its response could not be read.
+    Transport(reqwest::Error),
+
+    /// The server answered with an unexpected status and a body that is not an `AuthError`.
+    Server {
+        /// The HTTP status returned by the server.
+        status: StatusCode,
+
+        /// The raw response body.
+        body: String,
+    },
+
+    /// The server answered with a well-formed Yggdrasil error.
+    Auth(AuthError),
+
+    /// The response body is not valid UTF-8.
+    InvalidUtf8(std::str::Utf8Error),
+
+    /// The request body could not be encoded or the response body could not be decoded
+    /// into the expected JSON model.
+    Json(serde_json::Error),
+
+    /// The proxy configuration is invalid.
+    Proxy(reqwest::Error),
+
+    /// A URL could not be parsed.
+    InvalidUrl(url::ParseError),
+
+    /// The base URL uses a scheme other than `http` or `https`.
+    UnsupportedScheme(String),
+
+    /// A base64-encoded value could not be decoded.
+    Base64(base64::DecodeError),
+
+    /// A profile property was decoded as a property of a different name.
+    UnexpectedProperty(String),
+
+    /// The public key used to verify signatures cannot be parsed.
+    InvalidPublicKey(String),
+
+    /// The server does not publish a public key to verify signatures with.
+    MissingPublicKey,
+
+    /// The named profile property is unsigned or its signature does not match its value.
+    InvalidSignature(String),
+
+    /// The texture URL is not on the skin domain allow list.
+    UntrustedTextureDomain(String),
+
+    /// The access token was rejected, with the server's error if it sent one.
+    Unauthorized(Option<AuthError>),
+
+    /// The user may not perform the operation, with the server's error if it sent one.
+    Forbidden(Option<AuthError>),
+
+    /// The uploaded file is larger than the server accepts.
+    PayloadTooLarge,
+
+    /// The texture image was rejected before upload.
+    InvalidTexture(TextureImageError),
+
+    /// A file could not be read or written.
+    Io(std::io::Error),
+
+    /// A credential file could not be read or written.
+    Store(StoreError),
+
+    /// The player name is not accepted by the vanilla client.
+    InvalidPlayerName(String),
+
+    /// A profile or user UUID is malformed.
+    InvalidProfileId(String),
+
+    /// The profile is not one of the profiles available to the user.
+    ProfileNotAvailable(ProfileId),
+
+    /// A Microsoft OAuth endpoint returned an error.
+    OAuth(OAuthError),
+
+    /// Xbox Live refused to authenticate or authorise the user.
+    Xbox(XboxError),
+
+    /// An Xbox Live token carries no user hash, so it cannot be exchanged with Minecraft
+    /// services.
+    MissingXboxUserHash,
+}
+
+impl std::fmt::Display for YggdrasilError {
+    /// Formats the `YggdrasilError` for display.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to write to.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success or failure.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            YggdrasilError::Transport(error) => write!(f, "transport error: {}", error),
+            YggdrasilError::Server { status, body } => {
+                write!(f, "server status: {}, response: {}", status, body)
+            }
+            YggdrasilError::Auth(error) => write!(f, "{}", error),
+            YggdrasilError::InvalidUtf8(error) => write!(f, "invalid UTF-8 in response: {}", error),
+            YggdrasilError::Json(error) => write!(f, "invalid JSON: {}", error),
+            YggdrasilError::Proxy(error) => write!(f, "invalid proxy configuration: {}", error),
+            YggdrasilError::InvalidUrl(error) => write!(f, "invalid URL: {}", error),
+            YggdrasilError::UnsupportedScheme(scheme) => {
+                write!(f, "unsupported URL scheme: {}", scheme)
+            }
+            YggdrasilError::Base64(error) => write!(f, "invalid base64: {}", error),
+            YggdrasilError::UnexpectedProperty(name) => {
+                write!(f, "unexpected profile property: {}", name)
+            }
+            YggdrasilError::InvalidPublicKey(error) => write!(f, "invalid public key: {}", error),
+            YggdrasilError::MissingPublicKey => {
+                write!(f, "the server does not publish a signature public key")
+            }
+            YggdrasilError::InvalidSignature(name) => {
+                write!(f, "invalid signature on profile property: {}", name)
+            }
+            YggdrasilError::UntrustedTextureDomain(url) => {
+                write!(f, "untrusted texture domain: {}", url)
+            }
+            YggdrasilError::Unauthorized(Some(error)) => write!(f, "unauthorized: {}", error),
+            YggdrasilError::Unauthorized(None) => write!(f, "unauthorized"),
+            YggdrasilError::Forbidden(Some(error)) => write!(f, "forbidden: {}", error),
+            YggdrasilError::Forbidden(None) => write!(f, "forbidden"),
+            YggdrasilError::PayloadTooLarge => write!(f, "the uploaded file is too large"),
+            YggdrasilError::InvalidTexture(error) => write!(f, "invalid texture: {}", error),
+            YggdrasilError::Io(error) => write!(f, "I/O error: {}", error),
+            YggdrasilError::Store(error) => write!(f, "credential store error: {}", error),
+            YggdrasilError::InvalidPlayerName(name) => write!(f, "invalid player name: {}", name),
+            YggdrasilError::InvalidProfileId(uuid) => write!(f, "invalid UUID: {}", uuid),
+            YggdrasilError::ProfileNotAvailable(uuid) => {
+                write!(f, "the profile {} is not available to the user", uuid)
+            }
+            YggdrasilError::OAuth(error) => write!(f, "OAuth error: {}", error),
+            YggdrasilError::Xbox(error) => write!(f, "Xbox Live error: {}", error),
+            YggdrasilError::MissingXboxUserHash => {
+                write!(f, "the Xbox Live token has no user hash claim")
+            }
+        }
+    }
+}
+
+impl std::error::Error for YggdrasilError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            YggdrasilError::Transport(error) => Some(error),
+            YggdrasilError::Server { .. } => None,
+            YggdrasilError::Auth(error) => Some(error),
+            YggdrasilError::InvalidUtf8(error) => Some(error),
+            YggdrasilError::Json(error) => Some(error),
+            YggdrasilError::Proxy(error) => Some(error),
+            YggdrasilError::InvalidUrl(error) => Some(error),
+            YggdrasilError::UnsupportedScheme(_) => None,
+            YggdrasilError::Base64(error) => Some(error),
+            YggdrasilError::UnexpectedProperty(_) => None,
+            YggdrasilError::InvalidPublicKey(_) => None,
+            YggdrasilError::MissingPublicKey => None,
+            YggdrasilError::InvalidSignature(_) => None,
+            YggdrasilError::UntrustedTextureDomain(_) => None,
+            YggdrasilError::Unauthorized(error) => error.as_ref().map(|error| error as _),
+            YggdrasilError::Forbidden(error) => error.as_ref().map(|error| error as _),
+            YggdrasilError::PayloadTooLarge => None,
+            YggdrasilError::InvalidTexture(error) => Some(error),
+            YggdrasilError::Io(error) => Some(error),
+            YggdrasilError::Store(error) => Some(error),
+            YggdrasilError::InvalidPlayerName(_) => None,
+            YggdrasilError::InvalidProfileId(_) => None,
+            YggdrasilError::ProfileNotAvailable(_) => None,
+            YggdrasilError::OAuth(error) => Some(error),
+            YggdrasilError::Xbox(error) => Some(error),
+            YggdrasilError::MissingXboxUserHash => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for YggdrasilError {
+    fn from(error: reqwest::Error) -> Self {
+        YggdrasilError::Transport(error)
+    }
+}
+
+impl From<AuthError> for YggdrasilError {
+    fn from(error: AuthError) -> Self {
+        YggdrasilError::Auth(error)
+    }
+}
+
+impl From<std::str::Utf8Error> for YggdrasilError {
+    fn from(error: std::str::Utf8Error) -> Self {
+        YggdrasilError::InvalidUtf8(error)
+    }
+}
+
+impl From<serde_json::Error> for YggdrasilError {
+    fn from(error: serde_json::Error) -> Self {
+        YggdrasilError::Json(error)
+    }
+}
+
+impl From<url::ParseError> for YggdrasilError {
+    fn from(error: url::ParseError) -> Self {
+        YggdrasilError::InvalidUrl(error)
+    }
+}
+
+impl From<base64::DecodeError> for YggdrasilError {
+    fn from(error: base64::DecodeError) -> Self {
+        YggdrasilError::Base64(error)
+    }
+}
+
+impl From<TextureImageError> for YggdrasilError {
+    fn from(error: TextureImageError) -> Self {
+        YggdrasilError::InvalidTexture(error)
+    }
+}
+
+impl From<std::io::Error> for YggdrasilError {
+    fn from(error: std::io::Error) -> Self {
+        YggdrasilError::Io(error)
+    }
+}
+
+impl From<StoreError> for YggdrasilError {
+    fn from(error: StoreError) -> Self {
+        YggdrasilError::Store(error)
+    }
+}
+
+impl From<XboxError> for YggdrasilError {
+    fn from(error: XboxError) -> Self {
+        YggdrasilError::Xbox(error)
+    }
+}
@@ -1,5 +1,8 @@
 // JSON models for networking.
 pub mod model {
+    // API metadata JSON model.
+    pub mod api_metadata; // Defines the JSON model for the metadata served at the API root.
+
     // Agent JSON model.
     pub mod auth_agent; // Defines the JSON model for authentication agents.
 
@@ -9,9 +12,18 @@ pub mod model {
     // User JSON model.
     pub mod auth_user; // Defines the JSON model for user information.
 
+    // Profile UUID type.
+    pub mod profile_id; // Defines the UUID type shared by profiles and users.
+
     // Profile JSON model.
     pub mod auth_profile; // Defines the JSON model for user profiles.
 
+    // Game profile JSON model.
+    pub mod game_profile; // Defines the JSON model for game profiles with their properties.
+
+    // Textures JSON model.
+    pub mod textures_payload; // Defines the JSON model for the decoded textures property.
+
     // JSON models for requests.
     pub mod request; // Contains request-related JSON models.
     pub use self::request::*; // Re-exports request models for easier access.
@@ -21,9 +33,58 @@ pub mod model {
     pub use self::response::*; // Re-exports response models for easier access.
 }
 
+// Error type shared by all operations.
+pub mod error; // Defines the error type returned by the client.
+
+// Cryptographic helpers for the session server.
+pub mod crypto {
+    pub mod server_hash; // Computes the server ID hash used by the join handshake.
+
+    pub mod signature; // Verifies the signatures of profile properties.
+}
+
+// Helpers for handling textures.
+pub mod texture {
+    pub mod skin_domain; // Checks texture URLs against the skin domain allow list.
+
+    pub mod skin_image; // Validates and normalises texture images before upload.
+}
+
+// Account and session management.
+pub mod account {
+    pub mod session; // Keeps the tokens of a logged-in user valid.
+
+    pub mod token_store; // Persists accounts across launcher restarts.
+
+    pub mod encrypted_store; // Encrypts persisted accounts with a passphrase.
+
+    pub mod manager; // Tracks the accounts of a launcher across servers.
+
+    pub mod offline; // Accounts for offline play.
+}
+
+// Microsoft account sign-in.
+pub mod microsoft {
+    pub mod endpoints; // Configures the URLs of the services involved in the sign-in.
+
+    pub mod model; // Defines the JSON models of Microsoft, Xbox Live and Minecraft services.
+
+    pub mod xbox_error; // Decodes the error codes returned by Xbox Live.
+
+    pub mod authenticator; // Runs the device code sign-in through to Minecraft services.
+}
+
 pub mod client {
+    #[allow(clippy::module_inception)]
     pub mod client; // Contains the client implementation for interacting with the authentication system.
+
+    pub mod builder; // Contains the builder for configuring the client.
+
+    pub mod profile_lookup; // Contains the result of batch profile lookups.
 }
 
 // Re-exports all models for easier access from the top level.
 pub use model::*;
+
+// Re-exports the error type for easier access from the top level.
+pub use error::YggdrasilError;
@@ -0,0 +1,437 @@
+use crate::error::YggdrasilError;
+use crate::microsoft::endpoints::MicrosoftEndpoints;
+use crate::microsoft::model::{
+    DeviceCode, Entitlements, LoginWithXboxRequest, MicrosoftLogin, MicrosoftToken,
+    MinecraftProfile, MinecraftToken, OAuthError, XboxAuthRequest, XboxToken, XboxUserProperties,
+    XstsProperties,
+};
+use crate::microsoft::xbox_error::XboxError;
+use reqwest::{Client, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// The scopes requested for Xbox Live sign-in and refresh tokens.
+const SCOPE: &str = "XboxLive.signin offline_access";
+
+/// The grant type of device code polling requests.
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// The number of seconds the polling interval grows by when the server asks to slow down.
+const SLOW_DOWN_STEP: u64 = 5;
+
+/// Signs users in with their Microsoft account and fetches their Minecraft credentials.
+///
+/// The sign-in goes through the OAuth device code grant, Xbox Live user authentication, XSTS
+/// authorisation and finally Minecraft services, which return the access token and profile
+/// used by the rest of the crate.
+pub struct MicrosoftAuthenticator {
+    /// The OAuth client ID of the application.
+    client_id: String,
+
+    /// The URLs of the services involved in the sign-in.
+    endpoints: MicrosoftEndpoints,
+
+    /// The HTTP client used to send requests.
+    http_client: Client,
+}
+
+impl MicrosoftAuthenticator {
+    /// Creates a new `MicrosoftAuthenticator` with a default HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The OAuth client ID of the application, registered with Microsoft.
+    /// * `endpoints` - The URLs of the services involved in the sign-in.
+    ///
+    /// # Returns
+    ///
+    /// A new `MicrosoftAuthenticator` instance.
+    pub fn new(client_id: &str, endpoints: MicrosoftEndpoints) -> MicrosoftAuthenticator {
+        MicrosoftAuthenticator::with_client(client_id, endpoints, Client::new())
+    }
+
+    /// Creates a new `MicrosoftAuthenticator` that sends requests with the given HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The OAuth client ID of the application, registered with Microsoft.
+    /// * `endpoints` - The URLs of the services involved in the sign-in.
+    /// * `http_client` - The HTTP client used to send requests.
+    ///
+    /// # Returns
+    ///
+    /// A new `MicrosoftAuthenticator` instance.
+    pub fn with_client(
+        client_id: &str,
+        endpoints: MicrosoftEndpoints,
+        http_client: Client,
+    ) -> MicrosoftAuthenticator {
+        MicrosoftAuthenticator {
+            client_id: client_id.to_string(),
+            endpoints,
+            http_client,
+        }
+    }
+
+    /// Requests a device code to start a sign-in.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `DeviceCode` to show to the user on success or a
+    /// `YggdrasilError` on failure.
+    pub async fn request_device_code(&self) -> Result<DeviceCode, YggdrasilError> {
+        let res = self
+            .http_client
+            .post(&self.endpoints.device_code)
+            .form(&[("client_id", self.client_id.as_str()), ("scope", SCOPE)])
+            .send()
+            .await?;
+        read_oauth_response(res).await
+    }
+
+    /// Polls the token endpoint until the user completes the sign-in.
+    ///
+    /// Polling waits for the interval of the device code between requests and backs off when
+    /// the server asks to slow down.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_code` - The device code returned by `request_device_code`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `MicrosoftToken` on success or a `YggdrasilError::OAuth` if the
+    /// user declined or the code expired.
+    pub async fn poll_device_code(
+        &self,
+        device_code: &DeviceCode,
+    ) -> Result<MicrosoftToken, YggdrasilError> {
+        let deadline = Instant::now() + Duration::from_secs(device_code.expires_in);
+        let mut interval = device_code.interval;
+
+        loop {
+            let res = self
+                .http_client
+                .post(&self.endpoints.token)
+                .form(&[
+                    ("grant_type", DEVICE_CODE_GRANT),
+                    ("client_id", self.client_id.as_str()),
+                    ("device_code", device_code.device_code.as_str()),
+                ])
+                .send()
+                .await?;
+
+            match read_oauth_response(res).await {
+                Err(YggdrasilError::OAuth(error)) if error.error == "authorization_pending" => {}
+                Err(YggdrasilError::OAuth(error)) if error.error == "slow_down" => {
+                    interval += SLOW_DOWN_STEP;
+                }
+                result => return result,
+            }
+
+            if Instant::now() + Duration::from_secs(interval) > deadline {
+                return Err(YggdrasilError::OAuth(OAuthError {
+                    error: "expired_token".to_string(),
+                    error_description: Some("the device code expired".to_string()),
+                }));
+            }
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    }
+
+    /// Exchanges a refresh token for a new Microsoft token, without user interaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `refresh_token` - The refresh token of a previous sign-in.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new `MicrosoftToken` on success or a `YggdrasilError` on
+    /// failure.
+    pub async fn refresh_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<MicrosoftToken, YggdrasilError> {
+        let res = self
+            .http_client
+            .post(&self.endpoints.token)
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", self.client_id.as_str()),
+                ("scope", SCOPE),
+                ("refresh_token", refresh_token),
+            ])
+            .send()
+            .await?;
+        read_oauth_response(res).await
+    }
+
+    /// Authenticates a user with Xbox Live.
+    ///
+    /// # Arguments
+    ///
+    /// * `microsoft_access_token` - The access token of a `MicrosoftToken`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the Xbox Live user token on success or a `YggdrasilError::Xbox` if
+    /// Xbox Live refuses the account.
+    pub async fn authenticate_xbox_user(
+        &self,
+        microsoft_access_token: &str,
+    ) -> Result<XboxToken, YggdrasilError> {
+        let request = XboxAuthRequest {
+            properties: XboxUserProperties {
+                auth_method: "RPS".to_string(),
+                site_name: "user.auth.xboxlive.com".to_string(),
+                rps_ticket: format!("d={}", microsoft_access_token),
+            },
+            relying_party: "http://auth.xboxlive.com".to_string(),
+            token_type: "JWT".to_string(),
+        };
+        let res = self
+            .post_json(&self.endpoints.xbox_user_authenticate, &request)
+            .await?;
+        read_xbox_response(res).await
+    }
+
+    /// Exchanges an Xbox Live user token for an XSTS token for Minecraft services.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_token` - The token returned by `authenticate_xbox_user`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the XSTS token on success or a `YggdrasilError::Xbox` if Xbox Live
+    /// refuses the account, e.g. because it has no Xbox account or belongs to a child.
+    pub async fn authorize_xsts(
+        &self,
+        user_token: &XboxToken,
+    ) -> Result<XboxToken, YggdrasilError> {
+        let request = XboxAuthRequest {
+            properties: XstsProperties {
+                sandbox_id: "RETAIL".to_string(),
+                user_tokens: vec![user_token.token.clone()],
+            },
+            relying_party: "rp://api.minecraftservices.com/".to_string(),
+            token_type: "JWT".to_string(),
+        };
+        let res = self
+            .post_json(&self.endpoints.xsts_authorize, &request)
+            .await?;
+        read_xbox_response(res).await
+    }
+
+    /// Exchanges an XSTS token for a Minecraft access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `xsts_token` - The token returned by `authorize_xsts`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `MinecraftToken` on success or a `YggdrasilError` on failure,
+    /// such as `MissingXboxUserHash` if the token has no user claims.
+    pub async fn login_with_xbox(
+        &self,
+        xsts_token: &XboxToken,
+    ) -> Result<MinecraftToken, YggdrasilError> {
+        let user_hash = xsts_token
+            .user_hash()
+            .ok_or(YggdrasilError::MissingXboxUserHash)?;
+        let request = LoginWithXboxRequest {
+            identity_token: format!("XBL3.0 x={};{}", user_hash, xsts_token.token),
+        };
+        let res = self
+            .post_json(&self.endpoints.login_with_xbox, &request)
+            .await?;
+        read_json_response(res).await
+    }
+
+    /// Fetches the Minecraft profile of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `minecraft_access_token` - The access token of a `MinecraftToken`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `MinecraftProfile`, `None` if the user has not created a
+    /// profile yet (or does not own the game), or a `YggdrasilError` on failure.
+    pub async fn profile(
+        &self,
+        minecraft_access_token: &str,
+    ) -> Result<Option<MinecraftProfile>, YggdrasilError> {
+        let res = self
+            .http_client
+            .get(&self.endpoints.profile)
+            .bearer_auth(minecraft_access_token)
+            .send()
+            .await?;
+
+        // Minecraft services answer 404 for accounts without a profile
+        if res.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        read_json_response(res).await.map(Some)
+    }
+
+    /// Lists the products owned by a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `minecraft_access_token` - The access token of a `MinecraftToken`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Entitlements` on success or a `YggdrasilError` on failure.
+    /// Use `Entitlements::owns_game` to tell whether the user owns the game.
+    pub async fn entitlements(
+        &self,
+        minecraft_access_token: &str,
+    ) -> Result<Entitlements, YggdrasilError> {
+        let res = self
+            .http_client
+            .get(&self.endpoints.entitlements)
+            .bearer_auth(minecraft_access_token)
+            .send()
+            .await?;
+        read_json_response(res).await
+    }
+
+    /// Completes a sign-in from a Microsoft token, through Xbox Live to Minecraft services.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The Microsoft token returned by `poll_device_code` or `refresh_token`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `MicrosoftLogin` on success or a `YggdrasilError` on failure.
+    pub async fn login(&self, token: &MicrosoftToken) -> Result<MicrosoftLogin, YggdrasilError> {
+        let user_token = self.authenticate_xbox_user(&token.access_token).await?;
+        let xsts_token = self.authorize_xsts(&user_token).await?;
+        let minecraft_token = self.login_with_xbox(&xsts_token).await?;
+        let entitlements = self.entitlements(&minecraft_token.access_token).await?;
+        let profile = self.profile(&minecraft_token.access_token).await?;
+
+        Ok(MicrosoftLogin {
+            access_token: minecraft_token.access_token,
+            expires_in: minecraft_token.expires_in,
+            refresh_token: token.refresh_token.clone(),
+            owns_game: entitlements.owns_game(),
+            profile,
+        })
+    }
+
+    /// Sends a JSON POST request.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the endpoint.
+    /// * `body` - The request body.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response.
+    async fn post_json<B: Serialize>(
+        &self,
+        url: &str,
+        body: &B,
+    ) -> Result<Response, YggdrasilError> {
+        let res = self
+            .http_client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .body(serde_json::to_string(body)?)
+            .send()
+            .await?;
+        Ok(res)
+    }
+}
+
+/// Decodes the JSON response of an OAuth endpoint.
+///
+/// # Arguments
+///
+/// * `res` - The response.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded response, or a `YggdrasilError::OAuth` if the server
+/// returned an OAuth error.
+async fn read_oauth_response<T: DeserializeOwned>(res: Response) -> Result<T, YggdrasilError> {
+    let status = res.status();
+    let body = res.bytes().await?;
+    if status.is_success() {
+        return Ok(serde_json::from_slice(&body)?);
+    }
+
+    match serde_json::from_slice::<OAuthError>(&body) {
+        Ok(error) => Err(YggdrasilError::OAuth(error)),
+        Err(_) => Err(server_error(status, &body)),
+    }
+}
+
+/// Decodes the JSON response of an Xbox Live endpoint.
+///
+/// # Arguments
+///
+/// * `res` - The response.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded response, or a `YggdrasilError::Xbox` if the server
+/// returned an `XErr` code.
+async fn read_xbox_response<T: DeserializeOwned>(res: Response) -> Result<T, YggdrasilError> {
+    let status = res.status();
+    let body = res.bytes().await?;
+    if status.is_success() {
+        return Ok(serde_json::from_slice(&body)?);
+    }
+
+    match serde_json::from_slice::<XboxError>(&body) {
+        Ok(error) => Err(YggdrasilError::Xbox(error)),
+        Err(_) => Err(server_error(status, &body)),
+    }
+}
+
+/// Decodes the JSON response of a Minecraft services endpoint.
+///
+/// # Arguments
+///
+/// * `res` - The response.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded response.
+async fn read_json_response<T: DeserializeOwned>(res: Response) -> Result<T, YggdrasilError> {
+    let status = res.status();
+    let body = res.bytes().await?;
+    if status.is_success() {
+        Ok(serde_json::from_slice(&body)?)
+    } else {
+        Err(server_error(status, &body))
+    }
+}
+
+/// Builds the error for an unexpected response.
+///
+/// # Arguments
+///
+/// * `status` - The status of the response.
+/// * `body` - The body of the response.
+///
+/// # Returns
+///
+/// A `YggdrasilError::Server` holding the status and body.
+fn server_error(status: StatusCode, body: &[u8]) -> YggdrasilError {
+    YggdrasilError::Server {
+        status,
+        body: String::from_utf8_lossy(body).into_owned(),
+    }
+}
@@ -0,0 +1,69 @@
+/// The URLs of the services involved in a Microsoft account sign-in.
+///
+/// The defaults point at the production services; every URL can be replaced, e.g. to run
+/// the flow against a local mock server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MicrosoftEndpoints {
+    /// The OAuth endpoint issuing device codes.
+    pub device_code: String,
+
+    /// The OAuth endpoint issuing access and refresh tokens.
+    pub token: String,
+
+    /// The Xbox Live endpoint authenticating a user with a Microsoft access token.
+    pub xbox_user_authenticate: String,
+
+    /// The Xbox Live endpoint issuing XSTS tokens.
+    pub xsts_authorize: String,
+
+    /// The Minecraft services endpoint exchanging an XSTS token for an access token.
+    pub login_with_xbox: String,
+
+    /// The Minecraft services endpoint returning the profile of the user.
+    pub profile: String,
+
+    /// The Minecraft services endpoint listing the products the user owns.
+    pub entitlements: String,
+}
+
+impl Default for MicrosoftEndpoints {
+    /// Returns the endpoints of the production services.
+    fn default() -> Self {
+        MicrosoftEndpoints {
+            device_code: "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode"
+                .to_string(),
+            token: "https://login.microsoftonline.com/consumers/oauth2/v2.0/token".to_string(),
+            xbox_user_authenticate: "https://user.auth.xboxlive.com/user/authenticate".to_string(),
+            xsts_authorize: "https://xsts.auth.xboxlive.com/xsts/authorize".to_string(),
+            login_with_xbox: "https://api.minecraftservices.com/authentication/login_with_xbox"
+                .to_string(),
+            profile: "https://api.minecraftservices.com/minecraft/profile".to_string(),
+            entitlements: "https://api.minecraftservices.com/entitlements/mcstore".to_string(),
+        }
+    }
+}
+
+impl MicrosoftEndpoints {
+    /// Creates `MicrosoftEndpoints` that all live under a single base URL, using the paths of
+    /// the production services.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL, e.g. the URL of a local mock server.
+    ///
+    /// # Returns
+    ///
+    /// A new `MicrosoftEndpoints` instance.
+    pub fn with_base_url(base_url: &str) -> MicrosoftEndpoints {
+        let base_url = base_url.trim_end_matches('/');
+        MicrosoftEndpoints {
+            device_code: format!("{}/consumers/oauth2/v2.0/devicecode", base_url),
+            token: format!("{}/consumers/oauth2/v2.0/token", base_url),
+            xbox_user_authenticate: format!("{}/user/authenticate", base_url),
+            xsts_authorize: format!("{}/xsts/authorize", base_url),
+            login_with_xbox: format!("{}/authentication/login_with_xbox", base_url),
+            profile: format!("{}/minecraft/profile", base_url),
+            entitlements: format!("{}/entitlements/mcstore", base_url),
+        }
+    }
+}
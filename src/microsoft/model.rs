@@ -0,0 +1,392 @@
+use crate::auth_profile::AuthProfile;
+use crate::profile_id::ProfileId;
+use crate::textures_payload::SkinModel;
+use crate::AuthResponse;
+use serde::{Deserialize, Serialize};
+
+/// The polling interval, in seconds, used when the server does not suggest one.
+fn default_interval() -> u64 {
+    5
+}
+
+/// The device code issued to start a device code sign-in.
+///
+/// The user must open `verification_uri` and enter `user_code` while the launcher polls for
+/// the token.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceCode {
+    /// The code the launcher polls the token endpoint with.
+    pub device_code: String,
+
+    /// The code the user enters on the verification page.
+    pub user_code: String,
+
+    /// The page the user signs in on.
+    pub verification_uri: String,
+
+    /// The number of seconds before the codes expire.
+    pub expires_in: u64,
+
+    /// The number of seconds to wait between polls.
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+
+    /// The instructions to show to the user, if any.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// A Microsoft OAuth token.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MicrosoftToken {
+    /// The access token used to authenticate with Xbox Live.
+    pub access_token: String,
+
+    /// The refresh token, present when the `offline_access` scope was granted.
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+
+    /// The number of seconds before the access token expires.
+    pub expires_in: u64,
+}
+
+/// An error returned by the Microsoft OAuth endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OAuthError {
+    /// The error code, e.g. `authorization_pending` or `expired_token`.
+    pub error: String,
+
+    /// A human-readable description of the error, if any.
+    #[serde(default)]
+    pub error_description: Option<String>,
+}
+
+impl std::fmt::Display for OAuthError {
+    /// Formats the `OAuthError` for display.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to write to.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success or failure.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.error_description {
+            Some(description) => write!(f, "{}: {}", self.error, description),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for OAuthError {}
+
+/// The body of a request to an Xbox Live authentication endpoint.
+#[derive(Serialize)]
+pub struct XboxAuthRequest<P: Serialize> {
+    /// The properties of the request, which depend on the endpoint.
+    #[serde(rename = "Properties")]
+    pub properties: P,
+
+    /// The service the token is requested for.
+    #[serde(rename = "RelyingParty")]
+    pub relying_party: String,
+
+    /// The type of the token, always `JWT`.
+    #[serde(rename = "TokenType")]
+    pub token_type: String,
+}
+
+/// The properties of a request authenticating a user with Xbox Live.
+#[derive(Serialize)]
+pub struct XboxUserProperties {
+    /// The authentication method, always `RPS`.
+    #[serde(rename = "AuthMethod")]
+    pub auth_method: String,
+
+    /// The site the user authenticates to, always `user.auth.xboxlive.com`.
+    #[serde(rename = "SiteName")]
+    pub site_name: String,
+
+    /// The Microsoft access token, prefixed with `d=`.
+    #[serde(rename = "RpsTicket")]
+    pub rps_ticket: String,
+}
+
+/// The properties of a request for an XSTS token.
+#[derive(Serialize)]
+pub struct XstsProperties {
+    /// The sandbox, always `RETAIL`.
+    #[serde(rename = "SandboxId")]
+    pub sandbox_id: String,
+
+    /// The Xbox Live user tokens to authorise.
+    #[serde(rename = "UserTokens")]
+    pub user_tokens: Vec<String>,
+}
+
+/// The claims of an Xbox Live user.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct XboxUserClaims {
+    /// The user hash, needed to build the identity token.
+    pub uhs: String,
+}
+
+/// The claims attached to an Xbox Live token.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct XboxDisplayClaims {
+    /// The claims of each user the token is for.
+    pub xui: Vec<XboxUserClaims>,
+}
+
+/// A token issued by Xbox Live or the XSTS service.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct XboxToken {
+    /// The token.
+    #[serde(rename = "Token")]
+    pub token: String,
+
+    /// The claims attached to the token.
+    #[serde(rename = "DisplayClaims")]
+    pub display_claims: XboxDisplayClaims,
+}
+
+impl XboxToken {
+    /// Returns the hash of the user the token is for.
+    ///
+    /// # Returns
+    ///
+    /// The user hash, or `None` if the token has no user claims.
+    pub fn user_hash(&self) -> Option<&str> {
+        self.display_claims
+            .xui
+            .first()
+            .map(|claims| claims.uhs.as_str())
+    }
+}
+
+/// The body of a request exchanging an XSTS token for a Minecraft access token.
+#[derive(Serialize)]
+pub struct LoginWithXboxRequest {
+    /// The identity token, `XBL3.0 x=<user hash>;<XSTS token>`.
+    #[serde(rename = "identityToken")]
+    pub identity_token: String,
+}
+
+/// A Minecraft services access token.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MinecraftToken {
+    /// The access token passed to the game.
+    pub access_token: String,
+
+    /// The number of seconds before the access token expires.
+    pub expires_in: u64,
+}
+
+/// The names of the entitlements granting ownership of Minecraft: Java Edition.
+const GAME_ENTITLEMENTS: [&str; 2] = ["product_minecraft", "game_minecraft"];
+
+/// A product owned by a user.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EntitlementItem {
+    /// The name of the product, e.g. `product_minecraft`.
+    pub name: String,
+
+    /// The JWT proving the entitlement.
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// The products owned by a user, as listed by Minecraft services.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Entitlements {
+    /// The owned products; empty if the user owns none.
+    #[serde(default)]
+    pub items: Vec<EntitlementItem>,
+
+    /// The JWT signing the whole list, if any.
+    #[serde(default)]
+    pub signature: Option<String>,
+
+    /// The ID of the key the list is signed with, if any.
+    #[serde(rename = "keyId", default)]
+    pub key_id: Option<String>,
+}
+
+impl Entitlements {
+    /// Checks whether the user owns Minecraft: Java Edition.
+    ///
+    /// Users who do not own the game can only play the demo.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the list contains a game entitlement.
+    pub fn owns_game(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| GAME_ENTITLEMENTS.contains(&item.name.as_str()))
+    }
+}
+
+/// Whether a skin or cape is the one currently worn.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureState {
+    /// The texture is worn.
+    #[serde(rename = "ACTIVE")]
+    Active,
+
+    /// The texture is owned but not worn.
+    #[serde(rename = "INACTIVE")]
+    Inactive,
+
+    /// A state not known to this crate.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A skin of a Minecraft profile.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileSkin {
+    /// The ID of the skin.
+    pub id: String,
+
+    /// Whether the skin is worn.
+    pub state: TextureState,
+
+    /// The URL of the skin texture.
+    pub url: String,
+
+    /// The model of the skin, `CLASSIC` or `SLIM`.
+    #[serde(default)]
+    pub variant: Option<String>,
+
+    /// The hash of the skin texture, if any.
+    #[serde(rename = "textureKey", default)]
+    pub texture_key: Option<String>,
+
+    /// The name of a default skin, e.g. `STEVE`, if the skin is one.
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+impl ProfileSkin {
+    /// Returns the model of the skin.
+    ///
+    /// # Returns
+    ///
+    /// `SkinModel::Slim` if the variant is `SLIM`, `SkinModel::Classic` otherwise.
+    pub fn model(&self) -> SkinModel {
+        match self.variant.as_deref() {
+            Some(variant) if variant.eq_ignore_ascii_case("slim") => SkinModel::Slim,
+            _ => SkinModel::Classic,
+        }
+    }
+}
+
+/// A cape of a Minecraft profile.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileCape {
+    /// The ID of the cape.
+    pub id: String,
+
+    /// Whether the cape is worn.
+    pub state: TextureState,
+
+    /// The URL of the cape texture.
+    pub url: String,
+
+    /// The name of the cape, e.g. `Migrator`, if any.
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+/// The Minecraft profile of a user, as returned by Minecraft services.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MinecraftProfile {
+    /// The UUID of the profile.
+    pub id: ProfileId,
+
+    /// The name of the profile.
+    pub name: String,
+
+    /// The skins owned by the profile.
+    #[serde(default)]
+    pub skins: Vec<ProfileSkin>,
+
+    /// The capes owned by the profile.
+    #[serde(default)]
+    pub capes: Vec<ProfileCape>,
+}
+
+impl MinecraftProfile {
+    /// Returns the skin currently worn.
+    ///
+    /// # Returns
+    ///
+    /// The active skin, or `None` if no skin is active.
+    pub fn active_skin(&self) -> Option<&ProfileSkin> {
+        self.skins
+            .iter()
+            .find(|skin| skin.state == TextureState::Active)
+    }
+
+    /// Returns the cape currently worn.
+    ///
+    /// # Returns
+    ///
+    /// The active cape, or `None` if no cape is worn.
+    pub fn active_cape(&self) -> Option<&ProfileCape> {
+        self.capes
+            .iter()
+            .find(|cape| cape.state == TextureState::Active)
+    }
+
+    /// Converts the profile into the shape used by the rest of the crate.
+    ///
+    /// # Returns
+    ///
+    /// An `AuthProfile` with the ID and name of the profile.
+    pub fn to_auth_profile(&self) -> AuthProfile {
+        AuthProfile::new(self.name.clone(), self.id)
+    }
+}
+
+/// The outcome of a complete Microsoft account sign-in.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MicrosoftLogin {
+    /// The Minecraft access token passed to the game.
+    pub access_token: String,
+
+    /// The number of seconds before the access token expires.
+    pub expires_in: u64,
+
+    /// The Microsoft refresh token, used to sign in again without user interaction.
+    pub refresh_token: Option<String>,
+
+    /// Whether the user owns the game; users who do not can only play the demo.
+    pub owns_game: bool,
+
+    /// The Minecraft profile of the user, or `None` if they have not created one yet.
+    pub profile: Option<MinecraftProfile>,
+}
+
+impl MicrosoftLogin {
+    /// Converts the sign-in into the shape returned by `authenticate`.
+    ///
+    /// Minecraft services have no client token, so it is left empty.
+    ///
+    /// # Returns
+    ///
+    /// An `AuthResponse` with the profile selected, or with no profiles if the user has not
+    /// created one yet.
+    pub fn to_auth_response(&self) -> AuthResponse {
+        let profile = self.profile.as_ref().map(MinecraftProfile::to_auth_profile);
+        AuthResponse {
+            access_token: self.access_token.clone(),
+            client_token: String::new(),
+            available_profiles: profile.iter().cloned().collect(),
+            selected_profile: profile,
+            user: None,
+        }
+    }
+}
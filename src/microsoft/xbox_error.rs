@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents an error returned by Xbox Live when authenticating or authorising a user.
+///
+/// The `XErr` code identifies why the account cannot sign in, and the redirect, when present,
+/// points at the page where the user can fix it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct XboxError {
+    /// The identity the error applies to.
+    #[serde(rename = "Identity", default)]
+    pub identity: Option<String>,
+
+    /// The numeric error code.
+    #[serde(rename = "XErr")]
+    pub code: u64,
+
+    /// The message sent by the server, often empty.
+    #[serde(rename = "Message", default)]
+    pub message: Option<String>,
+
+    /// The page where the user can resolve the error, if any.
+    #[serde(rename = "Redirect", default)]
+    pub redirect: Option<String>,
+}
+
+/// Well-known kinds of Xbox Live errors, decoded from their `XErr` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum XboxErrorKind {
+    /// The account has been banned from Xbox Live.
+    Banned,
+
+    /// The account needs a guardian's permission to play online.
+    GuardianConsentRequired,
+
+    /// The Microsoft account has no Xbox account yet.
+    NoXboxAccount,
+
+    /// The account has not accepted the Xbox Live terms of service.
+    TermsNotAccepted,
+
+    /// The account is from a country where Xbox Live is not available.
+    CountryUnavailable,
+
+    /// The account must complete adult verification, as required in South Korea.
+    AdultVerificationRequired,
+
+    /// The account belongs to a child and must be added to a family by an adult.
+    ChildAccountNeedsFamily,
+
+    /// The code does not match any of the known kinds.
+    Unknown,
+}
+
+impl XboxErrorKind {
+    /// Returns an explanation of the error that can be shown to the user.
+    ///
+    /// # Returns
+    ///
+    /// A sentence describing the problem and how to resolve it.
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            XboxErrorKind::Banned => "This account has been banned from Xbox Live.",
+            XboxErrorKind::GuardianConsentRequired => {
+                "This account needs a parent or guardian's permission to play online."
+            }
+            XboxErrorKind::NoXboxAccount => {
+                "This Microsoft account has no Xbox account. Sign in on xbox.com to create one."
+            }
+            XboxErrorKind::TermsNotAccepted => {
+                "This account must accept the Xbox Live terms of service on xbox.com."
+            }
+            XboxErrorKind::CountryUnavailable => {
+                "This account is from a country where Xbox Live is not available."
+            }
+            XboxErrorKind::AdultVerificationRequired => {
+                "This account must complete adult verification on the Xbox website."
+            }
+            XboxErrorKind::ChildAccountNeedsFamily => {
+                "This account belongs to a child and must be added to a Microsoft family by an adult."
+            }
+            XboxErrorKind::Unknown => "Xbox Live refused to sign in this account.",
+        }
+    }
+}
+
+impl XboxError {
+    /// Classifies the error into one of the well-known Xbox Live error kinds.
+    ///
+    /// # Returns
+    ///
+    /// The `XboxErrorKind` matching the `XErr` code, or `XboxErrorKind::Unknown` if the code
+    /// is not known.
+    pub fn kind(&self) -> XboxErrorKind {
+        match self.code {
+            2148916227 => XboxErrorKind::Banned,
+            2148916229 => XboxErrorKind::GuardianConsentRequired,
+            2148916233 => XboxErrorKind::NoXboxAccount,
+            2148916234 => XboxErrorKind::TermsNotAccepted,
+            2148916235 => XboxErrorKind::CountryUnavailable,
+            2148916236 | 2148916237 => XboxErrorKind::AdultVerificationRequired,
+            2148916238 => XboxErrorKind::ChildAccountNeedsFamily,
+            _ => XboxErrorKind::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for XboxError {
+    /// Formats the `XboxError` for display.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to write to.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success or failure.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} (XErr {})", self.kind().explanation(), self.code)?;
+        if let Some(redirect) = &self.redirect {
+            write!(f, " See {}", redirect)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for XboxError {}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Represents the links published in the metadata of a Yggdrasil server.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ApiLinks {
+    /// The homepage of the server, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+
+    /// The registration page of the server, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register: Option<String>,
+
+    /// Any other links published by the server.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Represents the `meta` section of the metadata of a Yggdrasil server.
+///
+/// This struct contains the server's name, its implementation details, links and the
+/// `feature.*` flags defined by authlib-injector.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ApiMeta {
+    /// The name of the server.
+    #[serde(rename = "serverName", skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+
+    /// The name of the server implementation.
+    #[serde(rename = "implementationName", skip_serializing_if = "Option::is_none")]
+    pub implementation_name: Option<String>,
+
+    /// The version of the server implementation.
+    #[serde(rename = "implementationVersion", skip_serializing_if = "Option::is_none")]
+    pub implementation_version: Option<String>,
+
+    /// Links published by the server.
+    #[serde(default)]
+    pub links: ApiLinks,
+
+    /// Whether users may log in with a profile name instead of an email address.
+    #[serde(rename = "feature.non_email_login", default)]
+    pub non_email_login: bool,
+
+    /// Whether the server supports the legacy skin API.
+    #[serde(rename = "feature.legacy_skin_api", default)]
+    pub legacy_skin_api: bool,
+
+    /// Whether the Mojang namespace is disabled.
+    #[serde(rename = "feature.no_mojang_namespace", default)]
+    pub no_mojang_namespace: bool,
+
+    /// Whether Mojang's anti-features are enabled.
+    #[serde(rename = "feature.enable_mojang_anti_features", default)]
+    pub enable_mojang_anti_features: bool,
+
+    /// Whether the server supports Minecraft's message signing keys.
+    #[serde(rename = "feature.enable_profile_key", default)]
+    pub enable_profile_key: bool,
+
+    /// Whether the server checks usernames.
+    #[serde(rename = "feature.username_check", default)]
+    pub username_check: bool,
+
+    /// Any other fields, including feature flags not known to this crate.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Represents the metadata document served at the API root of a Yggdrasil server.
+///
+/// This struct contains the server's `meta` section, the domains textures may be loaded
+/// from and the public key used to sign profile properties.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiMetadata {
+    /// Information about the server.
+    #[serde(default)]
+    pub meta: ApiMeta,
+
+    /// The domains textures may be loaded from.
+    #[serde(rename = "skinDomains", default)]
+    pub skin_domains: Vec<String>,
+
+    /// The PEM-encoded public key used to sign profile properties, if any.
+    #[serde(rename = "signaturePublickey", skip_serializing_if = "Option::is_none")]
+    pub signature_publickey: Option<String>,
+
+    /// Any other fields not known to this crate.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
@@ -1,10 +1,10 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Represents an authentication error in Yggdrasil's authentication system.
 ///
 /// This struct contains information about the error encountered during authentication,
 /// including a general error identifier, a detailed error message, and the cause of the error.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AuthError {
     /// A general identifier for the error.
     pub error: String,
@@ -13,8 +13,34 @@ pub struct AuthError {
     #[serde(rename = "errorMessage")]
     pub error_message: String,
 
-    /// The cause of the error.
-    pub cause: String,
+    /// The cause of the error, if the server provided one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cause: Option<String>,
+}
+
+/// Well-known kinds of authentication errors defined by the Yggdrasil specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthErrorKind {
+    /// The username or password is wrong.
+    InvalidCredentials,
+
+    /// The access token is invalid or has expired.
+    InvalidToken,
+
+    /// The access token already has a profile assigned and cannot select another one.
+    ProfileAlreadyAssigned,
+
+    /// The request contains an illegal argument.
+    IllegalArgument,
+
+    /// Too many requests were made in a short period of time.
+    TooManyRequests,
+
+    /// The account has been migrated and must log in with its email address.
+    UserMigrated,
+
+    /// The error does not match any of the known kinds.
+    Unknown,
 }
 
 impl AuthError {
@@ -24,18 +50,53 @@ impl AuthError {
     ///
     /// * `error` - A general identifier for the error.
     /// * `error_message` - A detailed message explaining the error.
-    /// * `cause` - The cause of the error.
+    /// * `cause` - The cause of the error, if any.
     ///
     /// # Returns
     ///
     /// A new `AuthError` instance.
-    pub fn new(error: String, error_message: String, cause: String) -> AuthError {
+    pub fn new(error: String, error_message: String, cause: Option<String>) -> AuthError {
         AuthError {
             error,
             error_message,
             cause,
         }
     }
+
+    /// Classifies the error into one of the well-known Yggdrasil error kinds.
+    ///
+    /// # Returns
+    ///
+    /// The `AuthErrorKind` matching the error identifier and message, or
+    /// `AuthErrorKind::Unknown` if the pair is not defined by the specification.
+    pub fn kind(&self) -> AuthErrorKind {
+        if self.cause.as_deref() == Some("UserMigratedException") {
+            return AuthErrorKind::UserMigrated;
+        }
+
+        match (self.error.as_str(), self.error_message.as_str()) {
+            ("ForbiddenOperationException", "Invalid token.") => AuthErrorKind::InvalidToken,
+            ("ForbiddenOperationException", "Invalid credentials.") => {
+                AuthErrorKind::TooManyRequests
+            }
+            ("ForbiddenOperationException", message)
+                if message.starts_with("Invalid credentials. Account migrated") =>
+            {
+                AuthErrorKind::UserMigrated
+            }
+            ("ForbiddenOperationException", message)
+                if message.starts_with("Invalid credentials.") =>
+            {
+                AuthErrorKind::InvalidCredentials
+            }
+            ("IllegalArgumentException", "Access token already has a profile assigned.") => {
+                AuthErrorKind::ProfileAlreadyAssigned
+            }
+            ("IllegalArgumentException", _) => AuthErrorKind::IllegalArgument,
+            ("TooManyRequestsException", _) => AuthErrorKind::TooManyRequests,
+            _ => AuthErrorKind::Unknown,
+        }
+    }
 }
 
 impl std::fmt::Display for AuthError {
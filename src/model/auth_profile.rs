@@ -1,15 +1,16 @@
+use crate::profile_id::ProfileId;
 use serde::{Deserialize, Serialize};
 
 /// Represents an authentication profile in Yggdrasil's authentication system.
 ///
 /// This struct contains information about a user's profile, including the profile's name and ID.
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct AuthProfile {
     /// The name of the authentication profile.
     pub name: String,
 
     /// The unique identifier for the authentication profile.
-    pub id: String,
+    pub id: ProfileId,
 }
 
 impl AuthProfile {
@@ -23,7 +24,7 @@ impl AuthProfile {
     /// # Returns
     ///
     /// A new `AuthProfile` instance.
-    pub fn new(name: String, id: String) -> AuthProfile {
+    pub fn new(name: String, id: ProfileId) -> AuthProfile {
         AuthProfile { name, id }
     }
 }
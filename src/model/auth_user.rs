@@ -1,9 +1,10 @@
-use serde::Deserialize;
+use crate::profile_id::ProfileId;
+use serde::{Deserialize, Serialize};
 
 /// Represents a user property in Yggdrasil's authentication system.
 ///
 /// This struct contains information about a specific property of a user, including its name and value.
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AuthUserProperty {
     /// The name of the user property.
     pub name: String,
@@ -15,10 +16,10 @@ pub struct AuthUserProperty {
 /// Represents a user in Yggdrasil's authentication system.
 ///
 /// This struct contains information about a user, including the user's unique identifier and a list of properties associated with the user.
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AuthUser {
     /// The unique identifier of the user.
-    pub id: String,
+    pub id: ProfileId,
 
     /// A list of properties associated with the user.
     pub properties: Vec<AuthUserProperty>,
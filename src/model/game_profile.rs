@@ -0,0 +1,89 @@
+use crate::error::YggdrasilError;
+use crate::profile_id::ProfileId;
+use crate::texture::skin_domain::SkinDomainPolicy;
+use crate::textures_payload::{TexturesPayload, TEXTURES_PROPERTY};
+use serde::{Deserialize, Serialize};
+
+/// Represents a property of a game profile in Yggdrasil's authentication system.
+///
+/// Properties such as `textures` carry a base64-encoded value and, when requested,
+/// a signature made with the server's private key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProfileProperty {
+    /// The name of the property.
+    pub name: String,
+
+    /// The value of the property.
+    pub value: String,
+
+    /// The base64-encoded signature of the value, if the server signed it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Represents a complete game profile in Yggdrasil's authentication system.
+///
+/// Unlike `AuthProfile`, this struct also carries the properties of the profile,
+/// such as its textures.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameProfile {
+    /// The unique identifier of the profile.
+    pub id: ProfileId,
+
+    /// The name of the profile.
+    pub name: String,
+
+    /// The properties of the profile.
+    #[serde(default)]
+    pub properties: Vec<ProfileProperty>,
+}
+
+impl GameProfile {
+    /// Returns the property with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the property, e.g. `"textures"`.
+    ///
+    /// # Returns
+    ///
+    /// The property, or `None` if the profile has no property with this name.
+    pub fn property(&self, name: &str) -> Option<&ProfileProperty> {
+        self.properties.iter().find(|property| property.name == name)
+    }
+
+    /// Decodes the `textures` property of the profile, rejecting textures from untrusted domains.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The skin domain policy of the server.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded `TexturesPayload`, or `None` if the profile has no
+    /// `textures` property, or a `YggdrasilError::UntrustedTextureDomain` if any texture is
+    /// hosted on a domain that is not allowed.
+    pub fn textures(
+        &self,
+        policy: &SkinDomainPolicy,
+    ) -> Result<Option<TexturesPayload>, YggdrasilError> {
+        self.property(TEXTURES_PROPERTY)
+            .map(|property| TexturesPayload::from_property(property, policy))
+            .transpose()
+    }
+
+    /// Decodes the `textures` property of the profile without checking the domains of its
+    /// textures.
+    ///
+    /// The URLs of the returned textures are untrusted; prefer `textures`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded `TexturesPayload`, or `None` if the profile has no
+    /// `textures` property.
+    pub fn textures_unchecked(&self) -> Result<Option<TexturesPayload>, YggdrasilError> {
+        self.property(TEXTURES_PROPERTY)
+            .map(TexturesPayload::from_property_unchecked)
+            .transpose()
+    }
+}
@@ -0,0 +1,176 @@
+use crate::error::YggdrasilError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+/// The positions of the dashes in the dashed form of a UUID.
+const DASH_POSITIONS: [usize; 4] = [8, 13, 18, 23];
+
+/// The UUID of a profile or user.
+///
+/// Yggdrasil servers send unsigned UUIDs (32 hexadecimal digits) while other systems often
+/// store the dashed form, so both are parsed and compared by value, ignoring case. The
+/// unsigned form is used for display and serialisation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProfileId(u128);
+
+impl ProfileId {
+    /// Creates a new `ProfileId` from its 128-bit value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the UUID, most significant bits first.
+    ///
+    /// # Returns
+    ///
+    /// A new `ProfileId` instance.
+    pub const fn from_u128(value: u128) -> ProfileId {
+        ProfileId(value)
+    }
+
+    /// Returns the 128-bit value of the UUID.
+    ///
+    /// # Returns
+    ///
+    /// The value of the UUID, most significant bits first.
+    pub const fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Parses a UUID in its unsigned or dashed form, in any case.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The UUID, e.g. `"069a79f444e94726a5befca90e38aaf5"` or
+    ///   `"069a79f4-44e9-4726-a5be-fca90e38aaf5"`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ProfileId`, or a `YggdrasilError` if the UUID is malformed.
+    pub fn parse(uuid: &str) -> Result<ProfileId, YggdrasilError> {
+        let invalid = || YggdrasilError::InvalidProfileId(uuid.to_string());
+
+        let digits: String = match uuid.len() {
+            32 => uuid.to_string(),
+            36 => {
+                let bytes = uuid.as_bytes();
+                if DASH_POSITIONS
+                    .iter()
+                    .any(|&position| bytes[position] != b'-')
+                {
+                    return Err(invalid());
+                }
+                uuid.chars().filter(|&c| c != '-').collect()
+            }
+            _ => return Err(invalid()),
+        };
+
+        // from_str_radix accepts a leading sign, which is not part of a UUID
+        if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        u128::from_str_radix(&digits, 16)
+            .map(ProfileId)
+            .map_err(|_| invalid())
+    }
+
+    /// Formats the UUID without dashes.
+    ///
+    /// # Returns
+    ///
+    /// The 32 lowercase hexadecimal digits of the UUID.
+    pub fn to_undashed(&self) -> String {
+        format!("{:032x}", self.0)
+    }
+
+    /// Formats the UUID with dashes.
+    ///
+    /// # Returns
+    ///
+    /// The UUID in the 8-4-4-4-12 form, in lowercase.
+    pub fn to_dashed(&self) -> String {
+        let undashed = self.to_undashed();
+        format!(
+            "{}-{}-{}-{}-{}",
+            &undashed[..8],
+            &undashed[8..12],
+            &undashed[12..16],
+            &undashed[16..20],
+            &undashed[20..]
+        )
+    }
+
+    /// Returns the version of the UUID.
+    ///
+    /// # Returns
+    ///
+    /// The version nibble: 3 for name-based offline UUIDs, 4 for random online UUIDs.
+    pub fn version(&self) -> u8 {
+        ((self.0 >> 76) & 0xf) as u8
+    }
+
+    /// Checks whether the UUID was derived from a player name for offline play.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the UUID is a version 3 UUID.
+    pub fn is_offline(&self) -> bool {
+        self.version() == 3
+    }
+
+    /// Checks whether the UUID was generated randomly, as online profiles are.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the UUID is a version 4 UUID.
+    pub fn is_online(&self) -> bool {
+        self.version() == 4
+    }
+}
+
+impl std::fmt::Display for ProfileId {
+    /// Formats the `ProfileId` without dashes.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to write to.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success or failure.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+impl FromStr for ProfileId {
+    type Err = YggdrasilError;
+
+    fn from_str(uuid: &str) -> Result<Self, Self::Err> {
+        ProfileId::parse(uuid)
+    }
+}
+
+impl PartialEq<str> for ProfileId {
+    fn eq(&self, other: &str) -> bool {
+        ProfileId::parse(other).is_ok_and(|other| *self == other)
+    }
+}
+
+impl PartialEq<&str> for ProfileId {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl Serialize for ProfileId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_undashed())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProfileId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let uuid = String::deserialize(deserializer)?;
+        ProfileId::parse(&uuid).map_err(serde::de::Error::custom)
+    }
+}
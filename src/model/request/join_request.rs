@@ -0,0 +1,46 @@
+use crate::profile_id::ProfileId;
+use serde::Serialize;
+
+/// A request struct for joining a server through Yggdrasil's session server.
+///
+/// This struct is used to represent the request a client sends before connecting
+/// to an online-mode server.
+#[derive(Serialize)]
+pub struct JoinRequest {
+    /// The access token of the user.
+    #[serde(rename = "accessToken")]
+    pub access_token: String,
+
+    /// The UUID of the profile joining the server.
+    #[serde(rename = "selectedProfile")]
+    pub selected_profile: ProfileId,
+
+    /// The server ID hash computed during the handshake.
+    #[serde(rename = "serverId")]
+    pub server_id: String,
+}
+
+impl JoinRequest {
+    /// Creates a new `JoinRequest` with the provided access token, profile and server ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the user.
+    /// * `selected_profile` - The UUID of the profile joining the server.
+    /// * `server_id` - The server ID hash computed during the handshake.
+    ///
+    /// # Returns
+    ///
+    /// A new `JoinRequest` instance.
+    pub fn new(
+        access_token: String,
+        selected_profile: ProfileId,
+        server_id: String,
+    ) -> JoinRequest {
+        JoinRequest {
+            access_token,
+            selected_profile,
+            server_id,
+        }
+    }
+}
@@ -4,6 +4,9 @@ pub use auth_request::*;
 mod invalidate_request;
 pub use invalidate_request::*;
 
+mod join_request;
+pub use join_request::*;
+
 mod refresh_request;
 pub use refresh_request::*;
 
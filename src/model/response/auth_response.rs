@@ -1,12 +1,12 @@
 use crate::auth_profile::AuthProfile;
 use crate::auth_user::AuthUser;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// A response struct for handling authentication results from Yggdrasil's authentication system.
 ///
 /// This struct represents the response data returned by the authentication server, which includes
 /// the access token, client token, available profiles, selected profile, and optional user information.
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AuthResponse {
     /// The access token assigned to the authenticated user.
     #[serde(rename = "accessToken")]
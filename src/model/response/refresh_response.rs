@@ -1,12 +1,12 @@
 use crate::auth_profile::AuthProfile;
 use crate::auth_user::AuthUser;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// A response struct for handling the result of a token refresh request in Yggdrasil's authentication system.
 ///
 /// This struct represents the response data returned after refreshing the user's access and client tokens,
 /// including an optional selected profile and user information.
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RefreshResponse {
     /// The new access token assigned after the refresh.
     #[serde(rename = "accessToken")]
@@ -0,0 +1,227 @@
+use crate::error::YggdrasilError;
+use crate::game_profile::ProfileProperty;
+use crate::profile_id::ProfileId;
+use crate::texture::skin_domain::SkinDomainPolicy;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// The name of the profile property carrying the textures.
+pub const TEXTURES_PROPERTY: &str = "textures";
+
+/// The arm model of a skin.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SkinModel {
+    /// The classic model, with arms 4 pixels wide ("Steve").
+    #[default]
+    #[serde(rename = "default")]
+    Classic,
+
+    /// The slim model, with arms 3 pixels wide ("Alex").
+    #[serde(rename = "slim")]
+    Slim,
+}
+
+/// The type of a texture that can be uploaded or deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureType {
+    /// The skin of a profile.
+    Skin,
+
+    /// The cape of a profile.
+    Cape,
+}
+
+impl TextureType {
+    /// Returns the name of the texture type used in API paths.
+    ///
+    /// # Returns
+    ///
+    /// `"skin"` or `"cape"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextureType::Skin => "skin",
+            TextureType::Cape => "cape",
+        }
+    }
+}
+
+/// Represents the metadata of a texture.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TextureMetadata {
+    /// The model of a skin, `"slim"` for slim skins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Any other metadata.
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+
+/// Represents a single texture, such as a skin or a cape.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Texture {
+    /// The URL of the texture image.
+    pub url: String,
+
+    /// The metadata of the texture, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<TextureMetadata>,
+}
+
+impl Texture {
+    /// Returns the skin model of the texture.
+    ///
+    /// # Returns
+    ///
+    /// `SkinModel::Slim` if the metadata marks the skin as slim, `SkinModel::Classic` otherwise.
+    pub fn model(&self) -> SkinModel {
+        let model = self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.model.as_deref());
+        match model {
+            Some("slim") => SkinModel::Slim,
+            _ => SkinModel::Classic,
+        }
+    }
+
+    /// Returns the URL of the texture if the skin domain policy allows it.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The skin domain policy of the server.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed URL, or a `YggdrasilError::UntrustedTextureDomain` if
+    /// the texture is hosted on a domain that is not allowed.
+    pub fn checked_url(&self, policy: &SkinDomainPolicy) -> Result<Url, YggdrasilError> {
+        policy.check(&self.url)
+    }
+
+    /// Returns the hash of the texture, which is the last path segment of its URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The skin domain policy of the server.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the texture hash, or `None` if the URL has an empty path, or a
+    /// `YggdrasilError::UntrustedTextureDomain` if the texture is hosted on a domain that is
+    /// not allowed.
+    pub fn hash(&self, policy: &SkinDomainPolicy) -> Result<Option<String>, YggdrasilError> {
+        let url = self.checked_url(policy)?;
+        match url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+        {
+            Some(hash) if !hash.is_empty() => Ok(Some(hash.to_string())),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Represents the textures of a profile, keyed by texture type.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Textures {
+    /// The skin of the profile, if any.
+    #[serde(rename = "SKIN", default, skip_serializing_if = "Option::is_none")]
+    pub skin: Option<Texture>,
+
+    /// The cape of the profile, if any.
+    #[serde(rename = "CAPE", default, skip_serializing_if = "Option::is_none")]
+    pub cape: Option<Texture>,
+
+    /// Any other textures, such as an elytra.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Texture>,
+}
+
+/// Represents the decoded value of a profile's `textures` property.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TexturesPayload {
+    /// The time the payload was generated, in milliseconds since the Unix epoch.
+    pub timestamp: i64,
+
+    /// The UUID of the profile.
+    #[serde(rename = "profileId")]
+    pub profile_id: ProfileId,
+
+    /// The name of the profile.
+    #[serde(rename = "profileName")]
+    pub profile_name: String,
+
+    /// Whether the property must be signed, if the server says so.
+    #[serde(
+        rename = "signatureRequired",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub signature_required: Option<bool>,
+
+    /// The textures of the profile.
+    #[serde(default)]
+    pub textures: Textures,
+}
+
+impl TexturesPayload {
+    /// Decodes a `textures` profile property, rejecting textures from untrusted domains.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The `textures` property of a profile.
+    /// * `policy` - The skin domain policy of the server.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded `TexturesPayload` on success or a `YggdrasilError` if
+    /// the property is not named `textures`, its value cannot be decoded, or any texture is
+    /// hosted on a domain that is not allowed (`UntrustedTextureDomain`).
+    pub fn from_property(
+        property: &ProfileProperty,
+        policy: &SkinDomainPolicy,
+    ) -> Result<TexturesPayload, YggdrasilError> {
+        let payload = TexturesPayload::from_property_unchecked(property)?;
+
+        let textures = &payload.textures;
+        for texture in textures
+            .skin
+            .iter()
+            .chain(textures.cape.iter())
+            .chain(textures.extra.values())
+        {
+            texture.checked_url(policy)?;
+        }
+
+        Ok(payload)
+    }
+
+    /// Decodes the base64 JSON value of a `textures` profile property without checking the
+    /// domains of its textures.
+    ///
+    /// The URLs of the returned textures are untrusted; prefer `from_property`.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The `textures` property of a profile.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded `TexturesPayload` on success or a `YggdrasilError` if
+    /// the property is not named `textures` or its value cannot be decoded.
+    pub fn from_property_unchecked(
+        property: &ProfileProperty,
+    ) -> Result<TexturesPayload, YggdrasilError> {
+        if property.name != TEXTURES_PROPERTY {
+            return Err(YggdrasilError::UnexpectedProperty(property.name.clone()));
+        }
+
+        let value = STANDARD.decode(&property.value)?;
+        let payload = serde_json::from_str(std::str::from_utf8(&value)?)?;
+        Ok(payload)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::api_metadata::ApiMetadata;
+use crate::error::YggdrasilError;
+use url::Url;
+
+/// The domains textures may always be loaded from, as defined by authlib-injector.
+pub const DEFAULT_SKIN_DOMAINS: [&str; 2] = [".minecraft.net", ".mojang.com"];
+
+/// The allow list of domains textures may be loaded from.
+///
+/// An entry starting with a dot, such as `.example.com`, matches every subdomain of that
+/// domain but not the domain itself; any other entry only matches the exact domain.
+#[derive(Debug, Clone)]
+pub struct SkinDomainPolicy {
+    /// The allowed domains, in lowercase.
+    domains: Vec<String>,
+}
+
+impl SkinDomainPolicy {
+    /// Creates a new `SkinDomainPolicy` allowing the given domains and the default ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `domains` - The allowed domains, e.g. `["example.com", ".example.com"]`.
+    ///
+    /// # Returns
+    ///
+    /// A new `SkinDomainPolicy` instance.
+    pub fn new(domains: &[String]) -> SkinDomainPolicy {
+        let domains = DEFAULT_SKIN_DOMAINS
+            .iter()
+            .map(|domain| domain.to_string())
+            .chain(domains.iter().map(|domain| domain.to_lowercase()))
+            .collect();
+        SkinDomainPolicy { domains }
+    }
+
+    /// Creates a new `SkinDomainPolicy` from the `skinDomains` published in a server's metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - The metadata fetched from the server.
+    ///
+    /// # Returns
+    ///
+    /// A new `SkinDomainPolicy` instance.
+    pub fn from_metadata(metadata: &ApiMetadata) -> SkinDomainPolicy {
+        SkinDomainPolicy::new(&metadata.skin_domains)
+    }
+
+    /// Checks whether a texture may be loaded from the given URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the texture.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the URL uses `http` or `https` and its host is on the allow list.
+    pub fn is_allowed(&self, url: &str) -> bool {
+        self.check(url).is_ok()
+    }
+
+    /// Checks a texture URL against the allow list.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the texture.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed URL if it is allowed, or a
+    /// `YggdrasilError::UntrustedTextureDomain` if it is not.
+    pub fn check(&self, url: &str) -> Result<Url, YggdrasilError> {
+        let untrusted = || YggdrasilError::UntrustedTextureDomain(url.to_string());
+
+        let parsed = Url::parse(url).map_err(|_| untrusted())?;
+        if parsed.scheme() != "http" && parsed.scheme() != "https" {
+            return Err(untrusted());
+        }
+
+        let host = parsed.host_str().ok_or_else(untrusted)?;
+        let allowed = self.domains.iter().any(|domain| {
+            if domain.starts_with('.') {
+                host.ends_with(domain.as_str())
+            } else {
+                host == domain
+            }
+        });
+
+        if allowed {
+            Ok(parsed)
+        } else {
+            Err(untrusted())
+        }
+    }
+}
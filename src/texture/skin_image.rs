@@ -0,0 +1,312 @@
+use crate::textures_payload::{SkinModel, TextureType};
+use png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+
+/// The signature every PNG file starts with.
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// The rectangles copied when converting a legacy 64x32 skin to the 64x64 layout, in
+/// 64x64 pixel units: `(x, y, dx, dy, width, height)`. Each rectangle is copied to
+/// `(x + dx, y + dy)` and mirrored horizontally, the same way Minecraft does.
+const LEGACY_SKIN_COPIES: [(i64, i64, i64, i64, i64, i64); 12] = [
+    (4, 16, 16, 32, 4, 4),
+    (8, 16, 16, 32, 4, 4),
+    (0, 20, 24, 32, 4, 12),
+    (4, 20, 16, 32, 4, 12),
+    (8, 20, 8, 32, 4, 12),
+    (12, 20, 16, 32, 4, 12),
+    (44, 16, -8, 32, 4, 4),
+    (48, 16, -8, 32, 4, 4),
+    (40, 20, 0, 32, 4, 12),
+    (44, 20, -8, 32, 4, 12),
+    (48, 20, -16, 32, 4, 12),
+    (52, 20, -8, 32, 4, 12),
+];
+
+/// The rectangles left transparent by slim skins, in 64x64 pixel units:
+/// `(x, y, width, height)`.
+const SLIM_ARM_GAPS: [(u32, u32, u32, u32); 2] = [(50, 16, 2, 4), (54, 20, 2, 12)];
+
+/// The reasons a texture image can be rejected before upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextureImageError {
+    /// The file does not start with the PNG signature.
+    NotPng,
+
+    /// The PNG file is malformed.
+    Decode(String),
+
+    /// The image dimensions are not valid for the texture type.
+    InvalidDimensions {
+        /// The type of the texture.
+        texture_type: TextureType,
+
+        /// The width of the image.
+        width: u32,
+
+        /// The height of the image.
+        height: u32,
+    },
+
+    /// The normalised image could not be encoded.
+    Encode(String),
+}
+
+impl std::fmt::Display for TextureImageError {
+    /// Formats the `TextureImageError` for display.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to write to.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success or failure.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TextureImageError::NotPng => write!(f, "the file is not a PNG image"),
+            TextureImageError::Decode(error) => write!(f, "the PNG image is malformed: {}", error),
+            TextureImageError::InvalidDimensions {
+                texture_type: TextureType::Skin,
+                width,
+                height,
+            } => write!(
+                f,
+                "a skin must be 64x64 or 64x32 or a multiple of it, not {}x{}",
+                width, height
+            ),
+            TextureImageError::InvalidDimensions {
+                texture_type: TextureType::Cape,
+                width,
+                height,
+            } => write!(
+                f,
+                "a cape must be 64x32 or 22x17 or a multiple of it, not {}x{}",
+                width, height
+            ),
+            TextureImageError::Encode(error) => {
+                write!(f, "the PNG image could not be encoded: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextureImageError {}
+
+/// A texture image that passed validation and was normalised for upload.
+#[derive(Debug, Clone)]
+pub struct ValidatedTexture {
+    /// The normalised PNG file, in RGBA and without ancillary chunks.
+    pub png: Vec<u8>,
+
+    /// The width of the image.
+    pub width: u32,
+
+    /// The height of the image.
+    pub height: u32,
+
+    /// The model guessed from the transparent arm pixels; always `Classic` for capes.
+    ///
+    /// This is only a hint for suggesting a model to the user: a slim skin painted in the unused
+    /// arm columns is detected as classic.
+    pub model: SkinModel,
+}
+
+/// Validates and normalises a texture image before upload.
+///
+/// The image must be a PNG with dimensions valid for the texture type: skins are 64x64 or
+/// legacy 64x32, capes are 64x32 or 22x17, each possibly scaled up by an integer factor.
+/// The skin model is detected from the arm pixels, legacy skins are converted to the 64x64
+/// layout, and the image is re-encoded without ancillary chunks.
+///
+/// # Arguments
+///
+/// * `texture_type` - The type of the texture.
+/// * `png` - The PNG file.
+///
+/// # Returns
+///
+/// A `Result` containing the `ValidatedTexture`, or a `TextureImageError` explaining why the
+/// image was rejected.
+pub fn validate_texture(
+    texture_type: TextureType,
+    png: &[u8],
+) -> Result<ValidatedTexture, TextureImageError> {
+    if !png.starts_with(&PNG_SIGNATURE) {
+        return Err(TextureImageError::NotPng);
+    }
+
+    let (mut image, width, mut height) = decode_rgba(png)?;
+
+    let invalid = || TextureImageError::InvalidDimensions {
+        texture_type,
+        width,
+        height,
+    };
+    let model = match texture_type {
+        TextureType::Skin => {
+            if width == 0 || width % 64 != 0 || (height != width && height * 2 != width) {
+                return Err(invalid());
+            }
+            if height == width {
+                detect_skin_model(&image, width)
+            } else {
+                // Legacy skins predate the slim model
+                image = convert_legacy_skin(&image, width);
+                height = width;
+                SkinModel::Classic
+            }
+        }
+        TextureType::Cape => {
+            let standard = width % 64 == 0 && height * 2 == width;
+            let legacy = width % 22 == 0 && height * 22 == width * 17;
+            if width == 0 || !(standard || legacy) {
+                return Err(invalid());
+            }
+            SkinModel::Classic
+        }
+    };
+
+    Ok(ValidatedTexture {
+        png: encode_rgba(&image, width, height)?,
+        width,
+        height,
+        model,
+    })
+}
+
+/// Decodes a PNG file into 8-bit RGBA pixels.
+///
+/// # Arguments
+///
+/// * `png` - The PNG file.
+///
+/// # Returns
+///
+/// A `Result` containing the pixels, the width and the height of the image.
+fn decode_rgba(png: &[u8]) -> Result<(Vec<u8>, u32, u32), TextureImageError> {
+    let decode_error = |error: png::DecodingError| TextureImageError::Decode(error.to_string());
+
+    let mut decoder = Decoder::new(png);
+    decoder.set_transformations(Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(decode_error)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(decode_error)?;
+    let pixels = &buffer[..info.buffer_size()];
+
+    let image = match info.color_type {
+        ColorType::Rgba => pixels.to_vec(),
+        ColorType::Rgb => pixels
+            .chunks_exact(3)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 0xff])
+            .collect(),
+        ColorType::GrayscaleAlpha => pixels
+            .chunks_exact(2)
+            .flat_map(|pixel| [pixel[0], pixel[0], pixel[0], pixel[1]])
+            .collect(),
+        ColorType::Grayscale => pixels
+            .iter()
+            .flat_map(|&gray| [gray, gray, gray, 0xff])
+            .collect(),
+        ColorType::Indexed => {
+            return Err(TextureImageError::Decode(
+                "indexed colors were not expanded".to_string(),
+            ))
+        }
+    };
+
+    Ok((image, info.width, info.height))
+}
+
+/// Encodes 8-bit RGBA pixels into a PNG file containing only critical chunks.
+///
+/// # Arguments
+///
+/// * `image` - The pixels of the image.
+/// * `width` - The width of the image.
+/// * `height` - The height of the image.
+///
+/// # Returns
+///
+/// A `Result` containing the PNG file.
+fn encode_rgba(image: &[u8], width: u32, height: u32) -> Result<Vec<u8>, TextureImageError> {
+    let encode_error = |error: png::EncodingError| TextureImageError::Encode(error.to_string());
+
+    let mut png = Vec::new();
+    let mut encoder = Encoder::new(&mut png, width, height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(encode_error)?;
+    writer.write_image_data(image).map_err(encode_error)?;
+    writer.finish().map_err(encode_error)?;
+
+    Ok(png)
+}
+
+/// Converts a legacy skin, half as high as it is wide, to the square layout.
+///
+/// The legacy layout has a single arm and leg, which are mirrored into the slots of the
+/// left arm and leg of the square layout.
+///
+/// # Arguments
+///
+/// * `image` - The RGBA pixels of the legacy skin.
+/// * `width` - The width of the skin.
+///
+/// # Returns
+///
+/// The RGBA pixels of the square skin.
+fn convert_legacy_skin(image: &[u8], width: u32) -> Vec<u8> {
+    let width = width as usize;
+    let scale = (width / 64) as i64;
+
+    let mut square = vec![0; width * width * 4];
+    square[..image.len()].copy_from_slice(image);
+
+    for (x, y, dx, dy, w, h) in LEGACY_SKIN_COPIES {
+        let (x, y, dx, dy, w, h) = (
+            x * scale,
+            y * scale,
+            dx * scale,
+            dy * scale,
+            w * scale,
+            h * scale,
+        );
+        for j in 0..h {
+            for i in 0..w {
+                let source = ((y + j) as usize * width + (x + i) as usize) * 4;
+                let target = ((y + dy + j) as usize * width + (x + dx + w - 1 - i) as usize) * 4;
+                square.copy_within(source..source + 4, target);
+            }
+        }
+    }
+
+    square
+}
+
+/// Detects whether a square skin uses the slim or classic model.
+///
+/// Slim skins leave the outer columns of the right arm transparent.
+///
+/// # Arguments
+///
+/// * `image` - The RGBA pixels of the square skin.
+/// * `width` - The width of the skin.
+///
+/// # Returns
+///
+/// The detected `SkinModel`.
+fn detect_skin_model(image: &[u8], width: u32) -> SkinModel {
+    let scale = width / 64;
+    let transparent = SLIM_ARM_GAPS.iter().all(|&(x, y, w, h)| {
+        (y * scale..(y + h) * scale).all(|row| {
+            (x * scale..(x + w) * scale)
+                .all(|column| image[((row * width + column) * 4 + 3) as usize] == 0)
+        })
+    });
+
+    if transparent {
+        SkinModel::Slim
+    } else {
+        SkinModel::Classic
+    }
+}
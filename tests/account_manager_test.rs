@@ -0,0 +1,374 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::invalid_token_body;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+    use std::error::Error;
+    use std::sync::Arc;
+    use yggdrasil_authenticator::account::manager::{AccountEvent, AccountManager, LoginOutcome};
+    use yggdrasil_authenticator::account::session::SessionStatus;
+    use yggdrasil_authenticator::account::offline::OFFLINE_SERVER;
+    use yggdrasil_authenticator::account::token_store::{JsonFileTokenStore, MemoryTokenStore, TokenStore};
+    use yggdrasil_authenticator::error::YggdrasilError;
+    use yggdrasil_authenticator::profile_id::ProfileId;
+
+    const NOTCH: &str = "069a79f444e94726a5befca90e38aaf5";
+    const JEB: &str = "853c80ef3c3749fdaa49938b674adae6";
+    const USER: &str = "b2b1b6a6f6d34a7f9a8e0f1c7d3e5a42";
+
+    // Helper function to create a manager backed by an in-memory store
+    fn create_test_manager() -> (AccountManager, Arc<MemoryTokenStore>) {
+        let store = Arc::new(MemoryTokenStore::new());
+        (AccountManager::new(store.clone(), "client_token"), store)
+    }
+
+    // Helper function to parse a profile UUID
+    fn profile_id(uuid: &str) -> ProfileId {
+        uuid.parse().unwrap()
+    }
+
+    // Helper function to build an authenticate response body
+    fn auth_body(access_token: &str, selected: Option<(&str, &str)>) -> String {
+        json!({
+            "accessToken": access_token,
+            "clientToken": "client_token",
+            "availableProfiles": [
+                {"id": NOTCH, "name": "Notch"},
+                {"id": JEB, "name": "jeb_"}
+            ],
+            "selectedProfile": selected.map(|(id, name)| json!({"id": id, "name": name})),
+            "user": {"id": USER, "properties": []}
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_add_account_selects_first_account() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a login with a selected profile
+        let _m = server.mock("POST", "/authenticate")
+            .match_body(Matcher::PartialJson(json!({"username": "notch@example.com", "requestUser": true})))
+            .with_status(200)
+            .with_body(auth_body("access_token", Some((NOTCH, "Notch"))))
+            .create_async()
+            .await;
+
+        let (manager, _) = create_test_manager();
+        let mut events = manager.subscribe();
+
+        let account = match manager.add_account(&server.url(), "notch@example.com", "password").await? {
+            LoginOutcome::LoggedIn(account) => account,
+            LoginOutcome::ProfileRequired(_) => panic!("expected a selected profile"),
+        };
+        assert_eq!(account.server, format!("{}/", server.url()));
+        assert_eq!(account.username.as_deref(), Some("notch@example.com"));
+
+        let selected = manager.selected()?.unwrap();
+        assert_eq!(selected.profile_id, NOTCH);
+        assert!(matches!(events.try_recv()?, AccountEvent::Added { .. }));
+        assert!(matches!(events.try_recv()?, AccountEvent::Selected { .. }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_selection_survives_restart() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.json");
+
+        let manager = AccountManager::new(Arc::new(JsonFileTokenStore::open(&path)?), "client_token");
+        manager.add_offline_account("Notch")?;
+        let jeb = manager.add_offline_account("jeb_")?;
+        manager.select(OFFLINE_SERVER, &jeb.profile_id)?;
+        drop(manager);
+
+        // A new manager over the reopened file picks up the selection
+        let manager = AccountManager::new(Arc::new(JsonFileTokenStore::open(&path)?), "client_token");
+        assert_eq!(manager.selected()?.unwrap().profile_id, jeb.profile_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_choose_profile_replaces_previous_profile() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a login without a selected profile
+        let _m = server.mock("POST", "/authenticate")
+            .with_status(200)
+            .with_body(auth_body("unbound_token", None))
+            .create_async()
+            .await;
+
+        // Mock binding the token to each profile
+        let _n = server.mock("POST", "/refresh")
+            .match_body(Matcher::PartialJson(json!({"selectedProfile": {"id": NOTCH}})))
+            .with_status(200)
+            .with_body(json!({
+                "accessToken": "notch_token",
+                "clientToken": "client_token",
+                "selectedProfile": {"id": NOTCH, "name": "Notch"},
+                "user": {"id": USER, "properties": []}
+            }).to_string())
+            .create_async()
+            .await;
+        let _j = server.mock("POST", "/refresh")
+            .match_body(Matcher::PartialJson(json!({"selectedProfile": {"id": JEB}})))
+            .with_status(200)
+            .with_body(json!({
+                "accessToken": "jeb_token",
+                "clientToken": "client_token",
+                "selectedProfile": {"id": JEB, "name": "jeb_"},
+                "user": {"id": USER, "properties": []}
+            }).to_string())
+            .create_async()
+            .await;
+
+        let (manager, store) = create_test_manager();
+        let response = match manager.add_account(&server.url(), "user", "password").await? {
+            LoginOutcome::ProfileRequired(response) => response,
+            LoginOutcome::LoggedIn(_) => panic!("expected a profile choice"),
+        };
+        let notch = response.available_profiles[0].clone();
+        let jeb = response.available_profiles[1].clone();
+
+        manager.choose_profile(&server.url(), "user", &response, &notch).await?;
+        let account = manager.choose_profile(&server.url(), "user", &response, &jeb).await?;
+        assert_eq!(account.access_token, "jeb_token");
+
+        // The user is kept once, and the selection follows them
+        let accounts = store.list()?;
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].profile_id, JEB);
+        assert_eq!(manager.selected()?.unwrap().profile_id, JEB);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_choose_profile_rejects_unavailable_profile() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a login without a selected profile, which must not be bound to a foreign profile
+        let _m = server.mock("POST", "/authenticate")
+            .with_status(200)
+            .with_body(auth_body("unbound_token", None))
+            .create_async()
+            .await;
+        let refresh = server.mock("POST", "/refresh")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let (manager, store) = create_test_manager();
+        let response = match manager.add_account(&server.url(), "user", "password").await? {
+            LoginOutcome::ProfileRequired(response) => response,
+            LoginOutcome::LoggedIn(_) => panic!("expected a profile choice"),
+        };
+        let mut foreign = response.available_profiles[0].clone();
+        foreign.id = profile_id(USER);
+
+        let result = manager.choose_profile(&server.url(), "user", &response, &foreign).await;
+        assert!(matches!(result, Err(YggdrasilError::ProfileNotAvailable(id)) if id == USER));
+        assert!(store.list()?.is_empty());
+        refresh.assert_async().await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ensure_valid_reports_invalidated_token() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        let _a = server.mock("POST", "/authenticate")
+            .with_status(200)
+            .with_body(auth_body("access_token", Some((NOTCH, "Notch"))))
+            .create_async()
+            .await;
+
+        // Mock a token that can neither be validated nor refreshed
+        let _v = server.mock("POST", "/validate")
+            .with_status(403)
+            .with_body(invalid_token_body())
+            .create_async()
+            .await;
+        let _r = server.mock("POST", "/refresh")
+            .with_status(403)
+            .with_body(invalid_token_body())
+            .create_async()
+            .await;
+
+        let (manager, _) = create_test_manager();
+        manager.add_account(&server.url(), "Notch", "password").await?;
+        let mut events = manager.subscribe();
+
+        let status = manager.ensure_valid(&server.url(), &profile_id(NOTCH)).await?;
+        assert_eq!(status, Some(SessionStatus::ReloginRequired));
+        assert_eq!(
+            events.recv().await?,
+            AccountEvent::TokenInvalidated {
+                server: format!("{}/", server.url()),
+                profile_id: profile_id(NOTCH),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ensure_valid_stores_refreshed_token() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        let _a = server.mock("POST", "/authenticate")
+            .with_status(200)
+            .with_body(auth_body("old_token", Some((NOTCH, "Notch"))))
+            .create_async()
+            .await;
+        let _v = server.mock("POST", "/validate")
+            .with_status(403)
+            .with_body(invalid_token_body())
+            .create_async()
+            .await;
+        let _r = server.mock("POST", "/refresh")
+            .match_body(Matcher::PartialJson(json!({"accessToken": "old_token"})))
+            .with_status(200)
+            .with_body(json!({
+                "accessToken": "new_token",
+                "clientToken": "client_token",
+                "selectedProfile": {"id": NOTCH, "name": "Notch"}
+            }).to_string())
+            .create_async()
+            .await;
+
+        let (manager, store) = create_test_manager();
+        manager.add_account(&server.url(), "Notch", "password").await?;
+
+        let status = manager.ensure_valid(&server.url(), &profile_id(NOTCH)).await?;
+        assert_eq!(status, Some(SessionStatus::Refreshed));
+        let server_root = format!("{}/", server.url());
+        assert_eq!(store.load(&server_root, &profile_id(NOTCH))?.unwrap().access_token, "new_token");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_waits_for_refresh() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        let _a = server.mock("POST", "/authenticate")
+            .with_status(200)
+            .with_body(auth_body("old_token", Some((NOTCH, "Notch"))))
+            .create_async()
+            .await;
+        let _v = server.mock("POST", "/validate")
+            .with_status(403)
+            .with_body(invalid_token_body())
+            .create_async()
+            .await;
+
+        // Mock a slow refresh, so the removal is requested while it is in flight
+        let _r = server.mock("POST", "/refresh")
+            .with_status(200)
+            .with_chunked_body(|writer| {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                writer.write_all(json!({
+                    "accessToken": "new_token",
+                    "clientToken": "client_token",
+                    "selectedProfile": {"id": NOTCH, "name": "Notch"}
+                }).to_string().as_bytes())
+            })
+            .create_async()
+            .await;
+        let _i = server.mock("POST", "/invalidate")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let (manager, store) = create_test_manager();
+        manager.add_account(&server.url(), "Notch", "password").await?;
+
+        let url = server.url();
+        let notch = profile_id(NOTCH);
+        let refresh = manager.ensure_valid(&url, &notch);
+        let remove = async {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            manager.remove(&url, &notch).await
+        };
+        let (status, removed) = tokio::join!(refresh, remove);
+
+        assert_eq!(status?, Some(SessionStatus::Refreshed));
+        assert_eq!(removed?.unwrap().access_token, "new_token");
+        assert!(store.list()?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_waits_for_login() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a slow login, so the removal is requested while it is in flight
+        let _a = server.mock("POST", "/authenticate")
+            .with_status(200)
+            .with_chunked_body(|writer| {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                writer.write_all(auth_body("login_token", Some((NOTCH, "Notch"))).as_bytes())
+            })
+            .create_async()
+            .await;
+        let _i = server.mock("POST", "/invalidate")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let (manager, store) = create_test_manager();
+        let url = server.url();
+        let notch = profile_id(NOTCH);
+        let login = manager.add_account(&url, "Notch", "password");
+        let remove = async {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            manager.remove(&url, &notch).await
+        };
+        let (outcome, removed) = tokio::join!(login, remove);
+
+        assert!(matches!(outcome?, LoginOutcome::LoggedIn(_)));
+        assert_eq!(removed?.unwrap().access_token, "login_token");
+        assert!(store.list()?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_relogin_and_remove() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        let _a = server.mock("POST", "/authenticate")
+            .match_body(Matcher::PartialJson(json!({"username": "notch@example.com"})))
+            .with_status(200)
+            .with_body(auth_body("access_token", Some((NOTCH, "Notch"))))
+            .expect(2)
+            .create_async()
+            .await;
+        let _i = server.mock("POST", "/invalidate")
+            .match_body(Matcher::PartialJson(json!({"accessToken": "access_token"})))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let (manager, store) = create_test_manager();
+        manager.add_account(&server.url(), "notch@example.com", "password").await?;
+
+        let account = manager.relogin(&server.url(), &profile_id(NOTCH), "password").await?;
+        assert!(account.is_some());
+
+        let removed = manager.remove(&server.url(), &profile_id(NOTCH)).await?;
+        assert!(removed.is_some());
+        assert!(store.list()?.is_empty());
+        assert!(manager.selected()?.is_none());
+
+        Ok(())
+    }
+}
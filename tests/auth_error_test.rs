@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use yggdrasil_authenticator::auth_error::{AuthError, AuthErrorKind};
+
+    #[test]
+    fn test_auth_error_kinds() {
+        for (error, message, cause, kind) in [
+            ("ForbiddenOperationException", "Invalid token.", None, AuthErrorKind::InvalidToken),
+            (
+                "ForbiddenOperationException",
+                "Invalid credentials. Invalid username or password.",
+                None,
+                AuthErrorKind::InvalidCredentials,
+            ),
+            // The bare message is what the specification sends for rate-limited logins
+            ("ForbiddenOperationException", "Invalid credentials.", None, AuthErrorKind::TooManyRequests),
+            (
+                "ForbiddenOperationException",
+                "Invalid credentials. Account migrated, use email as username.",
+                None,
+                AuthErrorKind::UserMigrated,
+            ),
+            (
+                "ForbiddenOperationException",
+                "Invalid credentials. Invalid username or password.",
+                Some("UserMigratedException"),
+                AuthErrorKind::UserMigrated,
+            ),
+            (
+                "IllegalArgumentException",
+                "Access token already has a profile assigned.",
+                None,
+                AuthErrorKind::ProfileAlreadyAssigned,
+            ),
+            ("IllegalArgumentException", "Invalid profile.", None, AuthErrorKind::IllegalArgument),
+            ("TooManyRequestsException", "Slow down.", None, AuthErrorKind::TooManyRequests),
+            ("ForbiddenOperationException", "Something else.", None, AuthErrorKind::Unknown),
+            ("InternalServerError", "Invalid token.", None, AuthErrorKind::Unknown),
+        ] {
+            let auth_error = AuthError::new(error.to_string(), message.to_string(), cause.map(str::to_string));
+            assert_eq!(auth_error.kind(), kind, "{}: {}", error, message);
+        }
+    }
+}
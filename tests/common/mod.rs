@@ -0,0 +1,43 @@
+// Each test file only uses some of the shared helpers
+#![allow(dead_code)]
+
+use png::{BitDepth, ColorType, Encoder};
+use serde_json::json;
+
+// Helper function to mock a rejected token
+pub fn invalid_token_body() -> String {
+    json!({
+        "error": "ForbiddenOperationException",
+        "errorMessage": "Invalid token."
+    })
+    .to_string()
+}
+
+// Helper function to encode RGBA pixels as a PNG with a text chunk
+pub fn encode_png(image: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = Encoder::new(&mut png, width, height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    encoder
+        .add_text_chunk("Comment".to_string(), "made by a test".to_string())
+        .unwrap();
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(image).unwrap();
+    writer.finish().unwrap();
+    png
+}
+
+// Helper function to create an opaque image
+pub fn opaque(width: u32, height: u32) -> Vec<u8> {
+    vec![0xff; (width * height * 4) as usize]
+}
+
+// Helper function to set the alpha of a rectangle
+pub fn clear(image: &mut [u8], width: u32, x: u32, y: u32, w: u32, h: u32) {
+    for row in y..y + h {
+        for column in x..x + w {
+            image[((row * width + column) * 4 + 3) as usize] = 0;
+        }
+    }
+}
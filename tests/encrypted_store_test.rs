@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use yggdrasil_authenticator::account::encrypted_store::{EncryptedFileTokenStore, KdfParams};
+    use yggdrasil_authenticator::account::token_store::{StoreError, StoredAccount, TokenStore};
+    use yggdrasil_authenticator::profile_id::ProfileId;
+    use yggdrasil_authenticator::YggdrasilError;
+
+    const SERVER: &str = "https://example.com/api/yggdrasil/";
+    const PROFILE_ID: ProfileId = ProfileId::from_u128(0x069a79f444e94726a5befca90e38aaf5);
+
+    // Cheap KDF parameters so the tests run quickly
+    const TEST_PARAMS: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    // Helper function to create an encrypted store holding one account
+    fn create_test_store(path: &std::path::Path) -> Result<(), Box<dyn Error>> {
+        let store = EncryptedFileTokenStore::open_with_params(path, "passphrase", TEST_PARAMS)?;
+        store.save(StoredAccount {
+            server: SERVER.to_string(),
+            profile_id: PROFILE_ID,
+            access_token: "secret_access_token".to_string(),
+            client_token: "client_token".to_string(),
+            selected_profile: None,
+            user: None,
+            username: None,
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_store_round_trip() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.bin");
+        create_test_store(&path)?;
+
+        // The token must not appear in plain text
+        let content = std::fs::read(&path)?;
+        assert!(!content
+            .windows(b"secret_access_token".len())
+            .any(|window| window == b"secret_access_token"));
+
+        // Default parameters are ignored in favour of the ones in the header
+        let store = EncryptedFileTokenStore::open(&path, "passphrase")?;
+        let account = store.load(SERVER, &PROFILE_ID)?.unwrap();
+        assert_eq!(account.access_token, "secret_access_token");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_store_rejects_wrong_passphrase() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.bin");
+        create_test_store(&path)?;
+
+        match EncryptedFileTokenStore::open(&path, "wrong") {
+            Err(YggdrasilError::Store(StoreError::Decryption)) => {}
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_store_rejects_tampering() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.bin");
+        create_test_store(&path)?;
+        let content = std::fs::read(&path)?;
+
+        // Flip a bit in the salt, which is only covered by the authentication tag
+        let mut tampered = content.clone();
+        tampered[20] ^= 1;
+        std::fs::write(&path, &tampered)?;
+        match EncryptedFileTokenStore::open(&path, "passphrase") {
+            Err(YggdrasilError::Store(StoreError::Decryption)) => {}
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+
+        // Flip a bit in the ciphertext
+        let mut tampered = content.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        std::fs::write(&path, &tampered)?;
+        match EncryptedFileTokenStore::open(&path, "passphrase") {
+            Err(YggdrasilError::Store(StoreError::Decryption)) => {}
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_store_rejects_oversized_kdf_params() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.bin");
+        create_test_store(&path)?;
+        let content = std::fs::read(&path)?;
+
+        // Raise memory, iterations and parallelism in turn; deriving with them would abort
+        // or hang the process
+        for offset in [5, 9, 13] {
+            let mut tampered = content.clone();
+            tampered[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+            std::fs::write(&path, &tampered)?;
+            match EncryptedFileTokenStore::open(&path, "passphrase") {
+                Err(YggdrasilError::Store(StoreError::InvalidKdfParams(_))) => {}
+                other => panic!("unexpected result: {:?}", other.err()),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_store_rejects_unknown_format() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.bin");
+        create_test_store(&path)?;
+
+        let mut content = std::fs::read(&path)?;
+        content[4] = 2;
+        std::fs::write(&path, &content)?;
+        match EncryptedFileTokenStore::open(&path, "passphrase") {
+            Err(YggdrasilError::Store(StoreError::UnsupportedVersion(2))) => {}
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+
+        std::fs::write(&path, b"{\"version\":1,\"accounts\":[]}")?;
+        match EncryptedFileTokenStore::open(&path, "passphrase") {
+            Err(YggdrasilError::Store(StoreError::NotEncrypted)) => {}
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+
+        Ok(())
+    }
+}
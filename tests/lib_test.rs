@@ -1,15 +1,37 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common::{clear, encode_png, opaque};
     use mockito::{Matcher, Server};
     use serde_json::json;
     use std::error::Error;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
     use yggdrasil_authenticator::auth_agent::AuthAgent;
-    use yggdrasil_authenticator::auth_error::AuthError;
+    use yggdrasil_authenticator::auth_error::AuthErrorKind;
+    use yggdrasil_authenticator::client::builder::ProxyScheme;
     use yggdrasil_authenticator::client::client::AuthClient;
+    use yggdrasil_authenticator::error::YggdrasilError;
+    use yggdrasil_authenticator::textures_payload::{SkinModel, TextureType};
 
     // Helper function to create a test client
     fn create_test_client(url: &str) -> AuthClient {
-        AuthClient::new(url.to_string(), None)
+        AuthClient::new(url.to_string(), None).unwrap()
+    }
+
+    // Helper function to create an opaque 64x64 skin
+    fn create_test_skin() -> Vec<u8> {
+        encode_png(&opaque(64, 64), 64, 64)
+    }
+
+    // Helper function to create a 64x64 skin with the transparent arm columns of the slim model
+    fn create_test_slim_skin() -> Vec<u8> {
+        let mut image = opaque(64, 64);
+        clear(&mut image, 64, 50, 16, 2, 4);
+        clear(&mut image, 64, 54, 20, 2, 12);
+        encode_png(&image, 64, 64)
     }
 
     #[tokio::test]
@@ -166,10 +188,689 @@ mod tests {
 
         assert!(result.is_err());
 
-        if let Err(e) = result {
-            let auth_error = e.downcast_ref::<AuthError>().unwrap();
+        if let Err(YggdrasilError::Auth(auth_error)) = result {
             assert_eq!(auth_error.error, "ForbiddenOperationException");
             assert_eq!(auth_error.error_message, "Invalid credentials. Invalid username or password.");
+            assert_eq!(auth_error.kind(), AuthErrorKind::InvalidCredentials);
+        } else {
+            panic!("expected an authentication error");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_without_cause() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a failed validate response whose error omits the cause
+        let _m = server.mock("POST", "/validate")
+            .with_status(403)
+            .with_body(json!({
+                "error": "ForbiddenOperationException",
+                "errorMessage": "Invalid token."
+            }).to_string())
+            .create();
+
+        let client = create_test_client(server.url().as_str());
+        let result = client.validate("expired_access_token").await;
+
+        match result {
+            Err(YggdrasilError::Auth(auth_error)) => {
+                assert_eq!(auth_error.cause, None);
+                assert_eq!(auth_error.kind(), AuthErrorKind::InvalidToken);
+            }
+            _ => panic!("expected an authentication error"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shared_http_client() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock two successful validate responses served over the same client
+        let m = server.mock("POST", "/validate")
+            .with_status(204)
+            .expect(2)
+            .create();
+
+        let client = AuthClient::with_client(server.url(), reqwest::Client::new())?;
+        client.validate("first_access_token").await?;
+        client.validate("second_access_token").await?;
+
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_proxy_url() {
+        let result = AuthClient::new("http://localhost".to_string(), Some("not a url".to_string()));
+
+        assert!(matches!(result, Err(YggdrasilError::Proxy(_))));
+    }
+
+    #[tokio::test]
+    async fn test_builder_user_agent_and_headers() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a validate response that requires the configured headers
+        let m = server.mock("POST", "/validate")
+            .match_header("user-agent", "TestLauncher/2.0")
+            .match_header("x-launcher-id", "abc")
+            .with_status(204)
+            .create();
+
+        let client = AuthClient::builder(server.url())
+            .user_agent("TestLauncher/2.0")
+            .default_header(
+                reqwest::header::HeaderName::from_static("x-launcher-id"),
+                reqwest::header::HeaderValue::from_static("abc"),
+            )
+            .connect_timeout(Duration::from_secs(5))
+            .read_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        client.validate("test_access_token").await?;
+
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_timeouts() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a validate response whose body arrives after half a second
+        let _m = server.mock("POST", "/validate")
+            .with_status(200)
+            .with_chunked_body(|writer| {
+                std::thread::sleep(Duration::from_millis(500));
+                writer.write_all(b"{}")
+            })
+            .create();
+
+        let client = AuthClient::builder(server.url())
+            .timeout(Duration::from_millis(100))
+            .build()?;
+        let result = client.validate("test_access_token").await;
+        assert!(matches!(result, Err(YggdrasilError::Transport(error)) if error.is_timeout()));
+
+        let client = AuthClient::builder(server.url())
+            .read_timeout(Duration::from_millis(100))
+            .build()?;
+        let result = client.validate("test_access_token").await;
+        assert!(matches!(result, Err(YggdrasilError::Transport(error)) if error.is_timeout()));
+
+        // Generous timeouts let the same response through
+        let client = AuthClient::builder(server.url())
+            .connect_timeout(Duration::from_secs(5))
+            .read_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(5))
+            .build()?;
+        client.validate("test_access_token").await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_http_proxy() -> Result<(), Box<dyn Error>> {
+        let mut proxy = Server::new_async().await;
+
+        // Mock a proxy that requires the configured credentials ("user:secret")
+        let m = proxy.mock("POST", "/api/yggdrasil/validate")
+            .match_header("proxy-authorization", "Basic dXNlcjpzZWNyZXQ=")
+            .with_status(204)
+            .create();
+
+        // The server does not exist, so the request only succeeds through the proxy
+        let client = AuthClient::builder("http://yggdrasil.invalid/api/yggdrasil".to_string())
+            .proxy(ProxyScheme::Http, &proxy.url())
+            .proxy_credentials("user", "secret")
+            .build()?;
+        client.validate("test_access_token").await?;
+
+        m.assert();
+
+        Ok(())
+    }
+
+    // Helper function to serve one SOCKS5 connection with username/password authentication,
+    // forwarding it to `target` and returning the credentials the client sent
+    async fn run_socks5_proxy(listener: TcpListener, target: String) -> (String, String) {
+        let (mut client, _) = listener.accept().await.unwrap();
+
+        // Greeting: the client must offer username/password authentication
+        let mut header = [0; 2];
+        client.read_exact(&mut header).await.unwrap();
+        let mut methods = vec![0; header[1] as usize];
+        client.read_exact(&mut methods).await.unwrap();
+        assert_eq!(header[0], 5);
+        assert!(methods.contains(&2));
+        client.write_all(&[5, 2]).await.unwrap();
+
+        // Username/password sub-negotiation
+        let mut length = [0; 2];
+        client.read_exact(&mut length).await.unwrap();
+        let mut username = vec![0; length[1] as usize];
+        client.read_exact(&mut username).await.unwrap();
+        client.read_exact(&mut length[..1]).await.unwrap();
+        let mut password = vec![0; length[0] as usize];
+        client.read_exact(&mut password).await.unwrap();
+        client.write_all(&[1, 0]).await.unwrap();
+
+        // Connect request, whose destination is ignored in favour of `target`
+        let mut request = [0; 4];
+        client.read_exact(&mut request).await.unwrap();
+        assert_eq!(request[1], 1);
+        let address_length = match request[3] {
+            1 => 4,
+            4 => 16,
+            _ => client.read_u8().await.unwrap() as usize,
+        };
+        let mut destination = vec![0; address_length + 2];
+        client.read_exact(&mut destination).await.unwrap();
+        client.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await.unwrap();
+
+        let mut upstream = TcpStream::connect(target).await.unwrap();
+        tokio::io::copy_bidirectional(&mut client, &mut upstream).await.ok();
+
+        (String::from_utf8(username).unwrap(), String::from_utf8(password).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_builder_socks5_proxy() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+        let m = server.mock("POST", "/api/yggdrasil/validate")
+            .with_status(204)
+            .create();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let proxy_url = format!("socks5h://{}", listener.local_addr()?);
+        let proxy = tokio::spawn(run_socks5_proxy(listener, server.host_with_port()));
+
+        // The server does not exist, so the request only succeeds through the proxy
+        let client = AuthClient::builder("http://yggdrasil.invalid/api/yggdrasil".to_string())
+            .proxy(ProxyScheme::All, &proxy_url)
+            .proxy_credentials("user", "secret")
+            .build()?;
+        client.validate("test_access_token").await?;
+        drop(client);
+
+        assert_eq!(proxy.await?, ("user".to_string(), "secret".to_string()));
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_base_url_with_trailing_slash() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a validate endpoint below a base path
+        let m = server.mock("POST", "/api/yggdrasil/validate")
+            .with_status(204)
+            .expect(2)
+            .create();
+
+        for base in ["/api/yggdrasil", "/api/yggdrasil/"] {
+            let client = create_test_client(&format!("{}{}", server.url(), base));
+            assert_eq!(client.base_url().path(), "/api/yggdrasil/");
+            client.validate("test_access_token").await?;
+        }
+
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_api_root() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock authentication endpoints below the base URL and metadata at the API root
+        let validate = server.mock("POST", "/api/yggdrasil/authserver/validate")
+            .with_status(204)
+            .create();
+        let metadata = server.mock("GET", "/api/yggdrasil/")
+            .with_status(200)
+            .with_body(json!({ "meta": {}, "skinDomains": [] }).to_string())
+            .create();
+
+        let client = AuthClient::builder(format!("{}/api/yggdrasil/authserver", server.url()))
+            .api_root(&format!("{}/api/yggdrasil", server.url()))
+            .build()?;
+        assert_eq!(client.base_url().path(), "/api/yggdrasil/authserver/");
+        assert_eq!(client.api_root().path(), "/api/yggdrasil/");
+
+        client.validate("test_access_token").await?;
+        client.metadata().await?;
+        validate.assert();
+        metadata.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_base_url_rejects_unsupported_scheme() {
+        let result = AuthClient::new("ftp://example.com/yggdrasil".to_string(), None);
+        assert!(matches!(result, Err(YggdrasilError::UnsupportedScheme(scheme)) if scheme == "ftp"));
+
+        let result = AuthClient::new("not a url".to_string(), None);
+        assert!(matches!(result, Err(YggdrasilError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_discover_follows_api_location() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a homepage indicating its API root with a relative location
+        let _home = server.mock("GET", "/")
+            .with_status(200)
+            .with_header("X-Authlib-Injector-API-Location", "/api/yggdrasil/")
+            .with_body("<html></html>")
+            .create();
+        let m = server.mock("POST", "/api/yggdrasil/authserver/validate")
+            .with_status(204)
+            .create();
+
+        let client = AuthClient::discover(&server.url()).await?;
+        assert_eq!(client.api_root().as_str(), format!("{}/api/yggdrasil/", server.url()));
+        assert_eq!(client.base_url().as_str(), format!("{}/api/yggdrasil/authserver/", server.url()));
+
+        client.validate("test_access_token").await?;
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_discover_without_api_location() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock an API root that does not send the header
+        let _root = server.mock("GET", "/api/yggdrasil")
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        // The scheme is omitted like a user would type it
+        let address = server.url().trim_start_matches("http://").to_string();
+        let result = AuthClient::discover(&format!("{}/api/yggdrasil", address)).await;
+
+        // Mockito only speaks plain HTTP, so the assumed https scheme cannot connect
+        assert!(matches!(result, Err(YggdrasilError::Transport(_))));
+
+        let client = AuthClient::discover(&format!("{}/api/yggdrasil", server.url())).await?;
+        assert_eq!(client.api_root().path(), "/api/yggdrasil/");
+        assert_eq!(client.base_url().path(), "/api/yggdrasil/authserver/");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metadata() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock the metadata document served at the API root
+        let _m = server.mock("GET", "/api/yggdrasil/")
+            .with_status(200)
+            .with_body(json!({
+                "meta": {
+                    "serverName": "Test Server",
+                    "implementationName": "yggdrasil-mock",
+                    "implementationVersion": "1.0.0",
+                    "links": {
+                        "homepage": "https://example.com/",
+                        "register": "https://example.com/register",
+                        "forum": "https://forum.example.com/"
+                    },
+                    "feature.non_email_login": true,
+                    "feature.custom_flag": 3
+                },
+                "skinDomains": ["example.com", ".example.com"],
+                "signaturePublickey": "-----BEGIN PUBLIC KEY-----\n-----END PUBLIC KEY-----\n",
+                "extraField": [1, 2]
+            }).to_string())
+            .create();
+
+        let client = create_test_client(&format!("{}/api/yggdrasil", server.url()));
+        let metadata = client.metadata().await?;
+
+        assert_eq!(metadata.meta.server_name.as_deref(), Some("Test Server"));
+        assert_eq!(metadata.meta.links.register.as_deref(), Some("https://example.com/register"));
+        assert_eq!(metadata.meta.links.extra["forum"], json!("https://forum.example.com/"));
+        assert!(metadata.meta.non_email_login);
+        assert!(!metadata.meta.legacy_skin_api);
+        assert_eq!(metadata.meta.extra["feature.custom_flag"], json!(3));
+        assert_eq!(metadata.skin_domains, vec!["example.com", ".example.com"]);
+        assert!(metadata.signature_publickey.is_some());
+        assert_eq!(metadata.extra["extraField"], json!([1, 2]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_join_success() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a successful join response (empty body)
+        let m = server.mock("POST", "/sessionserver/session/minecraft/join")
+            .match_body(Matcher::Json(json!({
+                "accessToken": "test_access_token",
+                "selectedProfile": "069a79f444e94726a5befca90e38aaf5",
+                "serverId": "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1"
+            })))
+            .with_status(204)
+            .create();
+
+        let client = create_test_client(server.url().as_str());
+        client
+            .join(
+                "test_access_token",
+                &"069a79f4-44e9-4726-a5be-fca90e38aaf5".parse()?,
+                "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1",
+            )
+            .await?;
+
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_has_joined() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a joined user and a user that has not joined
+        let _joined = server.mock("GET", "/sessionserver/session/minecraft/hasJoined")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("username".into(), "Notch".into()),
+                Matcher::UrlEncoded("serverId".into(), "server_hash".into()),
+                Matcher::UrlEncoded("ip".into(), "127.0.0.1".into()),
+            ]))
+            .with_status(200)
+            .with_body(json!({
+                "id": "069a79f444e94726a5befca90e38aaf5",
+                "name": "Notch",
+                "properties": [{
+                    "name": "textures",
+                    "value": "e30=",
+                    "signature": "c2lnbmF0dXJl"
+                }]
+            }).to_string())
+            .create();
+        let _not_joined = server.mock("GET", "/sessionserver/session/minecraft/hasJoined")
+            .match_query(Matcher::UrlEncoded("username".into(), "jeb_".into()))
+            .with_status(204)
+            .create();
+
+        let client = create_test_client(server.url().as_str());
+
+        let profile = client
+            .has_joined("Notch", "server_hash", Some("127.0.0.1"))
+            .await?
+            .expect("the user should have joined");
+        assert_eq!(profile.id, "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(profile.property("textures").unwrap().signature.as_deref(), Some("c2lnbmF0dXJl"));
+
+        let profile = client.has_joined("jeb_", "server_hash", None).await?;
+        assert!(profile.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_by_uuid() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock an existing profile with signed properties and a missing profile
+        let _found = server.mock("GET", "/sessionserver/session/minecraft/profile/069a79f444e94726a5befca90e38aaf5")
+            .match_query(Matcher::UrlEncoded("unsigned".into(), "false".into()))
+            .with_status(200)
+            .with_body(json!({
+                "id": "069a79f444e94726a5befca90e38aaf5",
+                "name": "Notch",
+                "properties": [
+                    {
+                        "name": "textures",
+                        "value": "e30=",
+                        "signature": "c2lnbmF0dXJl"
+                    },
+                    {
+                        "name": "uploadableTextures",
+                        "value": "skin,cape"
+                    }
+                ]
+            }).to_string())
+            .create();
+        let _missing = server.mock("GET", "/sessionserver/session/minecraft/profile/00000000000000000000000000000000")
+            .match_query(Matcher::UrlEncoded("unsigned".into(), "true".into()))
+            .with_status(204)
+            .create();
+
+        let client = create_test_client(server.url().as_str());
+
+        let profile = client
+            .profile_by_uuid(&"069a79f444e94726a5befca90e38aaf5".parse()?, true)
+            .await?
+            .expect("the profile should exist");
+        assert_eq!(profile.name, "Notch");
+        assert_eq!(profile.properties.len(), 2);
+        assert!(profile.property("textures").unwrap().signature.is_some());
+        assert!(profile.property("uploadableTextures").unwrap().signature.is_none());
+
+        let profile = client
+            .profile_by_uuid(&"00000000000000000000000000000000".parse()?, false)
+            .await?;
+        assert!(profile.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profiles_by_names() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock one response per batch of two names
+        let first = server.mock("POST", "/api/profiles/minecraft")
+            .match_body(Matcher::Json(json!(["notch", "jeb_"])))
+            .with_status(200)
+            .with_body(json!([
+                { "id": "069a79f444e94726a5befca90e38aaf5", "name": "Notch" },
+                { "id": "853c80ef3c3749fdaa49938b674adae6", "name": "jeb_" }
+            ]).to_string())
+            .create();
+        let second = server.mock("POST", "/api/profiles/minecraft")
+            .match_body(Matcher::Json(json!(["Dinnerbone", "ghost"])))
+            .with_status(200)
+            .with_body(json!([
+                { "id": "61699b2ed3274a019f1e0ea8c3f06bc6", "name": "Dinnerbone" }
+            ]).to_string())
+            .create();
+
+        let client = AuthClient::builder(server.url())
+            .profile_batch_size(2)
+            .profile_lookup_concurrency(2)
+            .build()?;
+        let lookup = client
+            .profiles_by_names(&["notch", "jeb_", "NOTCH", "Dinnerbone", "ghost"])
+            .await?;
+
+        first.assert();
+        second.assert();
+        assert_eq!(lookup.profiles.len(), 3);
+        assert_eq!(lookup.profiles["notch"].id, "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(lookup.profiles["Dinnerbone"].name, "Dinnerbone");
+        assert_eq!(lookup.missing, vec!["ghost"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_texture() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a successful multipart skin upload
+        let m = server.mock("PUT", "/api/user/profile/069a79f444e94726a5befca90e38aaf5/skin")
+            .match_header("authorization", "Bearer test_access_token")
+            .match_header("content-type", Matcher::Regex("^multipart/form-data".to_string()))
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex("name=\"model\"\r\n\r\nslim".to_string()),
+                Matcher::Regex("name=\"file\"; filename=\"skin.png\"".to_string()),
+                Matcher::Regex("IHDR".to_string()),
+            ]))
+            .with_status(204)
+            .create();
+
+        let client = create_test_client(server.url().as_str());
+        client
+            .upload_texture(
+                "test_access_token",
+                &"069a79f444e94726a5befca90e38aaf5".parse()?,
+                TextureType::Skin,
+                SkinModel::Slim,
+                create_test_slim_skin(),
+            )
+            .await?;
+
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_texture_keeps_given_model() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a slim upload and a classic upload, whose model field is empty
+        let slim = server.mock("PUT", "/api/user/profile/069a79f444e94726a5befca90e38aaf5/skin")
+            .match_body(Matcher::Regex("name=\"model\"\r\n\r\nslim".to_string()))
+            .with_status(204)
+            .expect(1)
+            .create();
+        let classic = server.mock("PUT", "/api/user/profile/069a79f444e94726a5befca90e38aaf5/skin")
+            .match_body(Matcher::Regex("name=\"model\"\r\n\r\n\r\n".to_string()))
+            .with_status(204)
+            .expect(1)
+            .create();
+
+        // The detected model is only a hint, so the given model is sent even when they differ
+        let client = create_test_client(server.url().as_str());
+        client
+            .upload_texture(
+                "test_access_token",
+                &"069a79f444e94726a5befca90e38aaf5".parse()?,
+                TextureType::Skin,
+                SkinModel::Slim,
+                create_test_skin(),
+            )
+            .await?;
+        client
+            .upload_texture(
+                "test_access_token",
+                &"069a79f444e94726a5befca90e38aaf5".parse()?,
+                TextureType::Skin,
+                SkinModel::Classic,
+                create_test_slim_skin(),
+            )
+            .await?;
+
+        slim.assert();
+        classic.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_texture_errors() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock rejections of the token, of the permission and of the file size
+        let _unauthorized = server.mock("DELETE", "/api/user/profile/069a79f444e94726a5befca90e38aaf5/cape")
+            .with_status(401)
+            .with_body(json!({
+                "error": "ForbiddenOperationException",
+                "errorMessage": "Invalid token."
+            }).to_string())
+            .create();
+        let _forbidden = server.mock("DELETE", "/api/user/profile/853c80ef3c3749fdaa49938b674adae6/skin")
+            .with_status(403)
+            .create();
+        let _too_large = server.mock("PUT", "/api/user/profile/069a79f444e94726a5befca90e38aaf5/skin")
+            .with_status(413)
+            .create();
+
+        let client = create_test_client(server.url().as_str());
+
+        let result = client
+            .delete_texture("expired_access_token", &"069a79f444e94726a5befca90e38aaf5".parse()?, TextureType::Cape)
+            .await;
+        match result {
+            Err(YggdrasilError::Unauthorized(Some(auth_error))) => {
+                assert_eq!(auth_error.kind(), AuthErrorKind::InvalidToken);
+            }
+            _ => panic!("expected an unauthorized error"),
+        }
+
+        let result = client
+            .delete_texture("test_access_token", &"853c80ef3c3749fdaa49938b674adae6".parse()?, TextureType::Skin)
+            .await;
+        assert!(matches!(result, Err(YggdrasilError::Forbidden(None))));
+
+        let result = client
+            .upload_texture(
+                "test_access_token",
+                &"069a79f444e94726a5befca90e38aaf5".parse()?,
+                TextureType::Skin,
+                SkinModel::Classic,
+                create_test_skin(),
+            )
+            .await;
+        assert!(matches!(result, Err(YggdrasilError::PayloadTooLarge)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_invalid_texture() {
+        let client = create_test_client("http://localhost");
+
+        // The image is rejected before any request is sent
+        let result = client
+            .upload_texture(
+                "test_access_token",
+                &"069a79f444e94726a5befca90e38aaf5".parse().unwrap(),
+                TextureType::Skin,
+                SkinModel::Classic,
+                b"not a png".to_vec(),
+            )
+            .await;
+        assert!(matches!(result, Err(YggdrasilError::InvalidTexture(_))));
+    }
+
+    #[tokio::test]
+    async fn test_non_json_error_response() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a failed validate response with a plain text body
+        let _m = server.mock("POST", "/validate")
+            .with_status(502)
+            .with_body("Bad Gateway")
+            .create();
+
+        let client = create_test_client(server.url().as_str());
+        let result = client.validate("test_access_token").await;
+
+        match result {
+            Err(YggdrasilError::Server { status, body }) => {
+                assert_eq!(status.as_u16(), 502);
+                assert_eq!(body, "Bad Gateway");
+            }
+            _ => panic!("expected a server error"),
         }
 
         Ok(())
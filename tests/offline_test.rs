@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::sync::Arc;
+    use yggdrasil_authenticator::account::manager::AccountManager;
+    use yggdrasil_authenticator::account::offline::{
+        offline_uuid, validate_player_name, OfflineAccount, OFFLINE_SERVER,
+    };
+    use yggdrasil_authenticator::account::session::SessionStatus;
+    use yggdrasil_authenticator::account::token_store::MemoryTokenStore;
+    use yggdrasil_authenticator::YggdrasilError;
+
+    #[test]
+    fn test_offline_uuid_matches_vanilla() {
+        assert_eq!(offline_uuid("Notch"), "b50ad385829d3141a2167e7d7539ba7f");
+        assert_eq!(offline_uuid("jeb_"), "a762f5604fce3236812ab80efff0b62b");
+    }
+
+    #[test]
+    fn test_validate_player_name() {
+        assert!(validate_player_name("Notch").is_ok());
+        assert!(validate_player_name("a_b").is_ok());
+        assert!(validate_player_name("sixteen_chars_ok").is_ok());
+
+        for name in ["ab", "seventeen_chars_x", "has space", "émile", ""] {
+            match validate_player_name(name) {
+                Err(YggdrasilError::InvalidPlayerName(invalid)) => assert_eq!(invalid, name),
+                other => panic!("unexpected result for {:?}: {:?}", name, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_offline_account_auth_response() -> Result<(), Box<dyn Error>> {
+        let account = OfflineAccount::new("Notch")?;
+        let response = account.to_auth_response();
+
+        assert_eq!(response.access_token, account.access_token());
+        assert_eq!(response.selected_profile.as_ref(), Some(account.profile()));
+        assert_eq!(response.available_profiles, vec![account.profile().clone()]);
+        assert_eq!(
+            OfflineAccount::new("Notch")?.access_token(),
+            account.access_token()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_manager_handles_offline_accounts() -> Result<(), Box<dyn Error>> {
+        let manager = AccountManager::new(Arc::new(MemoryTokenStore::new()), "client_token");
+
+        manager.add_offline_account("Notch")?;
+        let account = manager.add_offline_account("Notch")?;
+        assert_eq!(account.server, OFFLINE_SERVER);
+        assert_eq!(manager.accounts()?.len(), 1);
+
+        let status = manager
+            .ensure_valid(OFFLINE_SERVER, &account.profile_id)
+            .await?;
+        assert_eq!(status, Some(SessionStatus::Valid));
+
+        manager.remove(OFFLINE_SERVER, &account.profile_id).await?;
+        assert!(manager.accounts()?.is_empty());
+
+        Ok(())
+    }
+}
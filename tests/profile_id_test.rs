@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use yggdrasil_authenticator::account::offline::offline_uuid;
+    use yggdrasil_authenticator::auth_profile::AuthProfile;
+    use yggdrasil_authenticator::profile_id::ProfileId;
+    use yggdrasil_authenticator::YggdrasilError;
+
+    #[test]
+    fn test_parse_dashed_and_undashed() -> Result<(), Box<dyn Error>> {
+        let undashed = ProfileId::parse("069a79f444e94726a5befca90e38aaf5")?;
+        let dashed = ProfileId::parse("069A79F4-44E9-4726-A5BE-FCA90E38AAF5")?;
+
+        assert_eq!(undashed, dashed);
+        assert_eq!(dashed.to_string(), "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(dashed.to_dashed(), "069a79f4-44e9-4726-a5be-fca90e38aaf5");
+        assert_eq!(undashed, "069a79f4-44e9-4726-a5be-fca90e38aaf5");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_uuids() {
+        for uuid in [
+            "",
+            "069a79f444e94726a5befca90e38aaf",
+            "+69a79f444e94726a5befca90e38aaf5",
+            "069a79f444e94726a5befca90e38aafg",
+            "069a79f4-44e94-726-a5be-fca90e38aaf5",
+            "069a79f4-44e9-4726-a5be-fca90e38aaf5-",
+        ] {
+            match ProfileId::parse(uuid) {
+                Err(YggdrasilError::InvalidProfileId(invalid)) => assert_eq!(invalid, uuid),
+                other => panic!("unexpected result for {:?}: {:?}", uuid, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_serde_uses_undashed_form() -> Result<(), Box<dyn Error>> {
+        let profile: AuthProfile = serde_json::from_str(
+            r#"{"id": "069a79f4-44e9-4726-a5be-fca90e38aaf5", "name": "Notch"}"#,
+        )?;
+        assert_eq!(
+            serde_json::to_string(&profile)?,
+            r#"{"name":"Notch","id":"069a79f444e94726a5befca90e38aaf5"}"#
+        );
+
+        assert!(
+            serde_json::from_str::<AuthProfile>(r#"{"id": "Notch", "name": "Notch"}"#).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_version_detection() -> Result<(), Box<dyn Error>> {
+        let online = ProfileId::parse("069a79f444e94726a5befca90e38aaf5")?;
+        assert_eq!(online.version(), 4);
+        assert!(online.is_online() && !online.is_offline());
+
+        let offline = offline_uuid("Notch");
+        assert_eq!(offline.version(), 3);
+        assert!(offline.is_offline() && !offline.is_online());
+
+        Ok(())
+    }
+}
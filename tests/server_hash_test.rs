@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use yggdrasil_authenticator::crypto::server_hash::server_hash;
+
+    #[test]
+    fn test_server_hash_positive() {
+        assert_eq!(
+            server_hash("Notch", &[], &[]),
+            "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48"
+        );
+    }
+
+    #[test]
+    fn test_server_hash_negative() {
+        assert_eq!(
+            server_hash("jeb_", &[], &[]),
+            "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1"
+        );
+    }
+
+    #[test]
+    fn test_server_hash_strips_leading_zeros() {
+        assert_eq!(
+            server_hash("simon", &[], &[]),
+            "88e16a1019277b15d58faf0541e11910eb756f6"
+        );
+    }
+
+    #[test]
+    fn test_server_hash_concatenates_inputs() {
+        // Splitting the input across the three parts must not change the digest
+        assert_eq!(
+            server_hash("", b"jeb", b"_"),
+            server_hash("jeb_", &[], &[])
+        );
+    }
+}
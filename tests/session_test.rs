@@ -0,0 +1,100 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::invalid_token_body;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+    use std::error::Error;
+    use std::sync::Arc;
+    use yggdrasil_authenticator::account::session::{Session, SessionStatus, SessionTokens};
+    use yggdrasil_authenticator::auth_profile::AuthProfile;
+    use yggdrasil_authenticator::client::client::AuthClient;
+
+    // Helper function to create a session holding the given access token
+    fn create_test_session(url: &str, access_token: &str) -> Session {
+        let client = AuthClient::new(url.to_string(), None).unwrap();
+        Session::new(
+            Arc::new(client),
+            SessionTokens {
+                access_token: access_token.to_string(),
+                client_token: "client_token".to_string(),
+                selected_profile: Some(AuthProfile::new(
+                    "Notch".to_string(),
+                    "069a79f444e94726a5befca90e38aaf5".parse().unwrap(),
+                )),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_ensure_valid_with_valid_token() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a successful validate response
+        let _m = server.mock("POST", "/validate")
+            .with_status(204)
+            .create();
+
+        let session = create_test_session(server.url().as_str(), "valid_access_token");
+        assert_eq!(session.ensure_valid().await?, SessionStatus::Valid);
+        assert_eq!(session.tokens().await.access_token, "valid_access_token");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ensure_valid_refreshes_expired_token() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock a rejected validate response followed by a successful refresh
+        let _validate = server.mock("POST", "/validate")
+            .with_status(403)
+            .with_body(invalid_token_body())
+            .create();
+        let refresh = server.mock("POST", "/refresh")
+            .match_body(Matcher::PartialJson(json!({
+                "accessToken": "expired_access_token",
+                "clientToken": "client_token"
+            })))
+            .with_status(200)
+            .with_body(json!({
+                "accessToken": "new_access_token",
+                "clientToken": "client_token",
+                "selectedProfile": null,
+                "user": null
+            }).to_string())
+            .create();
+
+        let session = create_test_session(server.url().as_str(), "expired_access_token");
+        assert_eq!(session.ensure_valid().await?, SessionStatus::Refreshed);
+
+        refresh.assert();
+        let tokens = session.tokens().await;
+        assert_eq!(tokens.access_token, "new_access_token");
+        assert_eq!(tokens.selected_profile.unwrap().name, "Notch");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ensure_valid_requires_relogin() -> Result<(), Box<dyn Error>> {
+        let mut server = Server::new_async().await;
+
+        // Mock rejected validate and refresh responses
+        let _validate = server.mock("POST", "/validate")
+            .with_status(403)
+            .with_body(invalid_token_body())
+            .create();
+        let _refresh = server.mock("POST", "/refresh")
+            .with_status(403)
+            .with_body(invalid_token_body())
+            .create();
+
+        let session = create_test_session(server.url().as_str(), "revoked_access_token");
+        assert_eq!(session.ensure_valid().await?, SessionStatus::ReloginRequired);
+        assert_eq!(session.tokens().await.access_token, "revoked_access_token");
+
+        Ok(())
+    }
+}
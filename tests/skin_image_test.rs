@@ -0,0 +1,98 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{clear, encode_png, opaque};
+    use png::Decoder;
+    use std::error::Error;
+    use yggdrasil_authenticator::texture::skin_image::{validate_texture, TextureImageError};
+    use yggdrasil_authenticator::textures_payload::{SkinModel, TextureType};
+
+    // Helper function to decode a PNG into RGBA pixels
+    fn decode(png: &[u8]) -> Vec<u8> {
+        let mut reader = Decoder::new(png).read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_validate_classic_skin() -> Result<(), Box<dyn Error>> {
+        let texture = validate_texture(TextureType::Skin, &encode_png(&opaque(64, 64), 64, 64))?;
+
+        assert_eq!((texture.width, texture.height), (64, 64));
+        assert_eq!(texture.model, SkinModel::Classic);
+
+        // Ancillary chunks are stripped
+        assert!(!texture.png.windows(4).any(|chunk| chunk == b"tEXt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_slim_skin() -> Result<(), Box<dyn Error>> {
+        let mut image = opaque(128, 128);
+        clear(&mut image, 128, 100, 32, 4, 8);
+        clear(&mut image, 128, 108, 40, 4, 24);
+
+        let texture = validate_texture(TextureType::Skin, &encode_png(&image, 128, 128))?;
+        assert_eq!(texture.model, SkinModel::Slim);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_legacy_skin() -> Result<(), Box<dyn Error>> {
+        let mut image = vec![0; 64 * 32 * 4];
+        // Paint the leftmost column of the front of the leg
+        for row in 20..32 {
+            let offset = (row * 64 + 4) * 4;
+            image[offset..offset + 4].copy_from_slice(&[0xff, 0, 0, 0xff]);
+        }
+
+        let texture = validate_texture(TextureType::Skin, &encode_png(&image, 64, 32))?;
+        assert_eq!((texture.width, texture.height), (64, 64));
+        assert_eq!(texture.model, SkinModel::Classic);
+
+        // The left leg is the mirrored copy of the leg
+        let pixels = decode(&texture.png);
+        let offset = (52 * 64 + 23) * 4;
+        assert_eq!(&pixels[offset..offset + 4], &[0xff, 0, 0, 0xff]);
+        let offset = (52 * 64 + 20) * 4;
+        assert_eq!(pixels[offset + 3], 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_capes() -> Result<(), Box<dyn Error>> {
+        validate_texture(TextureType::Cape, &encode_png(&opaque(64, 32), 64, 32))?;
+        validate_texture(TextureType::Cape, &encode_png(&opaque(44, 34), 44, 34))?;
+
+        let result = validate_texture(TextureType::Cape, &encode_png(&opaque(64, 64), 64, 64));
+        assert_eq!(
+            result.unwrap_err(),
+            TextureImageError::InvalidDimensions {
+                texture_type: TextureType::Cape,
+                width: 64,
+                height: 64,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reject_invalid_files() {
+        let result = validate_texture(TextureType::Skin, b"GIF89a");
+        assert_eq!(result.unwrap_err(), TextureImageError::NotPng);
+
+        let mut png = encode_png(&opaque(64, 64), 64, 64);
+        png.truncate(40);
+        let result = validate_texture(TextureType::Skin, &png);
+        assert!(matches!(result, Err(TextureImageError::Decode(_))));
+
+        let result = validate_texture(TextureType::Skin, &encode_png(&opaque(64, 48), 64, 48));
+        assert!(matches!(result, Err(TextureImageError::InvalidDimensions { .. })));
+    }
+}
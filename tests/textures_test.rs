@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde_json::json;
+    use std::error::Error;
+    use yggdrasil_authenticator::api_metadata::ApiMetadata;
+    use yggdrasil_authenticator::error::YggdrasilError;
+    use yggdrasil_authenticator::game_profile::{GameProfile, ProfileProperty};
+    use yggdrasil_authenticator::texture::skin_domain::SkinDomainPolicy;
+    use yggdrasil_authenticator::textures_payload::{SkinModel, TexturesPayload};
+
+    // Helper function to create a textures property from its JSON value
+    fn textures_property(value: serde_json::Value) -> ProfileProperty {
+        ProfileProperty {
+            name: "textures".to_string(),
+            value: STANDARD.encode(value.to_string()),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_textures() -> Result<(), Box<dyn Error>> {
+        let property = textures_property(json!({
+            "timestamp": 1700000000000i64,
+            "profileId": "069a79f444e94726a5befca90e38aaf5",
+            "profileName": "Notch",
+            "textures": {
+                "SKIN": {
+                    "url": "https://textures.minecraft.net/texture/292009a4925b58f02c77dadc3ecef07ea4c7472f64e0fdc32ce5522489362680",
+                    "metadata": { "model": "slim" }
+                },
+                "CAPE": {
+                    "url": "https://textures.minecraft.net/texture/953cac8b779fe41383e675ee2b86071a71658f2180f56fbce8aa315ea70e2ed6"
+                }
+            }
+        }));
+
+        let policy = SkinDomainPolicy::new(&[]);
+        let payload = TexturesPayload::from_property(&property, &policy)?;
+        assert_eq!(payload.profile_name, "Notch");
+
+        let skin = payload.textures.skin.expect("the profile should have a skin");
+        assert_eq!(skin.model(), SkinModel::Slim);
+        assert_eq!(
+            skin.hash(&policy)?.as_deref(),
+            Some("292009a4925b58f02c77dadc3ecef07ea4c7472f64e0fdc32ce5522489362680")
+        );
+
+        let cape = payload.textures.cape.expect("the profile should have a cape");
+        assert_eq!(cape.model(), SkinModel::Classic);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_profile_textures() -> Result<(), Box<dyn Error>> {
+        let mut profile = GameProfile {
+            id: "069a79f444e94726a5befca90e38aaf5".parse()?,
+            name: "Notch".to_string(),
+            properties: Vec::new(),
+        };
+        let policy = SkinDomainPolicy::new(&[]);
+        assert!(profile.textures(&policy)?.is_none());
+
+        profile.properties.push(textures_property(json!({
+            "timestamp": 0,
+            "profileId": "069a79f444e94726a5befca90e38aaf5",
+            "profileName": "Notch",
+            "textures": {}
+        })));
+        let payload = profile.textures(&policy)?.expect("the profile should have textures");
+        assert!(payload.textures.skin.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_invalid_property() {
+        let property = ProfileProperty {
+            name: "uploadableTextures".to_string(),
+            value: "skin,cape".to_string(),
+            signature: None,
+        };
+        let result = TexturesPayload::from_property_unchecked(&property);
+        assert!(matches!(result, Err(YggdrasilError::UnexpectedProperty(name)) if name == "uploadableTextures"));
+
+        let property = ProfileProperty {
+            name: "textures".to_string(),
+            value: "not base64!".to_string(),
+            signature: None,
+        };
+        let result = TexturesPayload::from_property_unchecked(&property);
+        assert!(matches!(result, Err(YggdrasilError::Base64(_))));
+    }
+
+    #[test]
+    fn test_skin_domain_policy() -> Result<(), Box<dyn Error>> {
+        let metadata: ApiMetadata = serde_json::from_value(json!({
+            "meta": {},
+            "skinDomains": ["example.com", ".skins.example.org"]
+        }))?;
+        let policy = SkinDomainPolicy::from_metadata(&metadata);
+
+        assert!(policy.is_allowed("https://example.com/textures/abc"));
+        assert!(!policy.is_allowed("https://cdn.example.com/textures/abc"));
+        assert!(policy.is_allowed("https://a.skins.example.org/textures/abc"));
+        assert!(policy.is_allowed("http://b.a.skins.example.org/textures/abc"));
+        assert!(!policy.is_allowed("https://skins.example.org/textures/abc"));
+        assert!(!policy.is_allowed("https://evilskins.example.org/textures/abc"));
+        assert!(!policy.is_allowed("file://example.com/textures/abc"));
+
+        // The Mojang domains are always allowed
+        assert!(policy.is_allowed("https://textures.minecraft.net/texture/abc"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_textures_rejects_untrusted_domain() -> Result<(), Box<dyn Error>> {
+        let policy = SkinDomainPolicy::new(&["example.com".to_string()]);
+        let property = textures_property(json!({
+            "timestamp": 0,
+            "profileId": "069a79f444e94726a5befca90e38aaf5",
+            "profileName": "Notch",
+            "textures": {
+                "SKIN": { "url": "https://example.com/textures/skin" },
+                "CAPE": { "url": "https://evil.example.net/textures/cape" }
+            }
+        }));
+
+        let result = TexturesPayload::from_property(&property, &policy);
+        assert!(matches!(
+            result,
+            Err(YggdrasilError::UntrustedTextureDomain(url)) if url == "https://evil.example.net/textures/cape"
+        ));
+
+        // The unchecked helpers still decode the payload, but the hash of an untrusted
+        // texture is not returned
+        let payload = TexturesPayload::from_property_unchecked(&property)?;
+        let cape = payload.textures.cape.expect("the profile should have a cape");
+        assert!(matches!(cape.hash(&policy), Err(YggdrasilError::UntrustedTextureDomain(_))));
+
+        let profile = GameProfile {
+            id: "069a79f444e94726a5befca90e38aaf5".parse()?,
+            name: "Notch".to_string(),
+            properties: vec![property],
+        };
+        assert!(matches!(profile.textures(&policy), Err(YggdrasilError::UntrustedTextureDomain(_))));
+        assert!(profile.textures_unchecked()?.is_some());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::error::Error;
+    use yggdrasil_authenticator::account::token_store::{
+        JsonFileTokenStore, MemoryTokenStore, StoreError, StoredAccount, TokenStore,
+    };
+    use yggdrasil_authenticator::auth_profile::AuthProfile;
+    use yggdrasil_authenticator::profile_id::ProfileId;
+    use yggdrasil_authenticator::{AuthResponse, YggdrasilError};
+
+    const SERVER: &str = "https://example.com/api/yggdrasil/";
+
+    // Helper function to create an account for a profile
+    fn create_test_account(server: &str, profile_id: &str, access_token: &str) -> StoredAccount {
+        StoredAccount {
+            server: server.to_string(),
+            profile_id: profile_id.parse().unwrap(),
+            access_token: access_token.to_string(),
+            client_token: "client_token".to_string(),
+            selected_profile: Some(AuthProfile::new(
+                "Notch".to_string(),
+                profile_id.parse().unwrap(),
+            )),
+            user: None,
+            username: None,
+        }
+    }
+
+    #[test]
+    fn test_auth_response_round_trip() -> Result<(), Box<dyn Error>> {
+        let body = json!({
+            "accessToken": "access_token",
+            "clientToken": "client_token",
+            "availableProfiles": [{"id": "069a79f444e94726a5befca90e38aaf5", "name": "Notch"}],
+            "selectedProfile": {"id": "069a79f444e94726a5befca90e38aaf5", "name": "Notch"},
+            "user": {"id": "b2b1b6a6f6d34a7f9a8e0f1c7d3e5a42", "properties": [{"name": "preferredLanguage", "value": "en"}]}
+        });
+
+        let response: AuthResponse = serde_json::from_value(body.clone())?;
+        assert_eq!(serde_json::to_value(&response)?, body);
+
+        let account = StoredAccount::from_auth_response(SERVER, &response).unwrap();
+        assert_eq!(account.profile_id, "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(account.user.unwrap().properties[0].value, "en");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_store_keys_accounts_by_server_and_profile() -> Result<(), Box<dyn Error>> {
+        let store = MemoryTokenStore::new();
+        let other_server = "https://other.example.com/";
+
+        store.save(create_test_account(
+            SERVER,
+            "069a79f444e94726a5befca90e38aaf5",
+            "first",
+        ))?;
+        store.save(create_test_account(
+            other_server,
+            "069a79f444e94726a5befca90e38aaf5",
+            "second",
+        ))?;
+        store.save(create_test_account(
+            SERVER,
+            "069A79F444E94726A5BEFCA90E38AAF5",
+            "third",
+        ))?;
+
+        assert_eq!(store.list()?.len(), 2);
+        let account = store
+            .load(SERVER, &"069a79f444e94726a5befca90e38aaf5".parse()?)?
+            .unwrap();
+        assert_eq!(account.access_token, "third");
+
+        store.remove(other_server, &"069a79f444e94726a5befca90e38aaf5".parse()?)?;
+        assert!(store
+            .load(other_server, &"069a79f444e94726a5befca90e38aaf5".parse()?)?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_file_store_persists_accounts() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.json");
+
+        let store = JsonFileTokenStore::open(&path)?;
+        assert!(store.list()?.is_empty());
+        store.save(create_test_account(
+            SERVER,
+            "069a79f444e94726a5befca90e38aaf5",
+            "token",
+        ))?;
+        store.save(create_test_account(
+            SERVER,
+            "853c80ef3c3749fdaa49938b674adae6",
+            "other",
+        ))?;
+        store.remove(SERVER, &"853c80ef3c3749fdaa49938b674adae6".parse()?)?;
+
+        let reopened = JsonFileTokenStore::open(&path)?;
+        let accounts = reopened.list()?;
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].access_token, "token");
+        assert!(!directory.path().join("accounts.json.tmp").exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path)?.permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_file_store_persists_selection() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.json");
+        let notch: ProfileId = "069a79f444e94726a5befca90e38aaf5".parse()?;
+        let jeb: ProfileId = "853c80ef3c3749fdaa49938b674adae6".parse()?;
+
+        let store = JsonFileTokenStore::open(&path)?;
+        store.save(create_test_account(SERVER, &notch.to_string(), "token"))?;
+        store.save(create_test_account(SERVER, &jeb.to_string(), "other"))?;
+        store.select(Some((SERVER, &notch)))?;
+
+        let reopened = JsonFileTokenStore::open(&path)?;
+        assert_eq!(reopened.selected()?, Some((SERVER.to_string(), notch)));
+
+        // Removing the selected account clears the selection
+        reopened.remove(SERVER, &notch)?;
+        assert_eq!(JsonFileTokenStore::open(&path)?.selected()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_file_store_keeps_memory_on_failed_write() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.json");
+        let notch: ProfileId = "069a79f444e94726a5befca90e38aaf5".parse()?;
+
+        let store = JsonFileTokenStore::open(&path)?;
+        store.save(create_test_account(SERVER, &notch.to_string(), "token"))?;
+
+        // Block the temporary file, so every following write fails
+        std::fs::create_dir(directory.path().join("accounts.json.tmp"))?;
+
+        let jeb = create_test_account(SERVER, "853c80ef3c3749fdaa49938b674adae6", "other");
+        assert!(store.save(jeb).is_err());
+        assert!(store.select(Some((SERVER, &notch))).is_err());
+        assert!(store.remove(SERVER, &notch).is_err());
+
+        // Memory still matches the file
+        let accounts = store.list()?;
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].access_token, "token");
+        assert_eq!(store.selected()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_file_store_rejects_corrupt_file() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.json");
+        std::fs::write(&path, "not json")?;
+
+        assert!(JsonFileTokenStore::open(&path).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_file_store_rejects_unknown_version() -> Result<(), Box<dyn Error>> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("accounts.json");
+        std::fs::write(&path, r#"{"version":2,"accounts":[]}"#)?;
+
+        match JsonFileTokenStore::open(&path) {
+            Err(YggdrasilError::Store(StoreError::UnsupportedVersion(2))) => {}
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+
+        Ok(())
+    }
+}